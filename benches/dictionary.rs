@@ -0,0 +1,108 @@
+//! Compares the compiled dictionary with building a trie from the word list at startup, the way
+//! `words::load` used to.
+//!
+//! Run with `cargo bench --bench dictionary`.
+
+use spelltower::words;
+use std::time::{Duration, Instant};
+
+const DICTIONARY: &str = include_str!("../src/dictionary");
+
+#[derive(Default)]
+struct TrieNode {
+    children: [Option<Box<TrieNode>>; 26],
+    is_end: bool,
+}
+
+impl TrieNode {
+    fn build() -> TrieNode {
+        let mut root = TrieNode::default();
+        for word in DICTIONARY.lines() {
+            let mut node = &mut root;
+            for letter in word.bytes() {
+                node = node.children[(letter - b'A') as usize].get_or_insert_with(Default::default);
+            }
+            node.is_end = true;
+        }
+        root
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        word.bytes()
+            .try_fold(self, |node, letter| {
+                node.children[(letter - b'A') as usize].as_deref()
+            })
+            .is_some_and(|node| node.is_end)
+    }
+
+    fn nodes(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .flatten()
+            .map(|child| child.nodes())
+            .sum::<usize>()
+    }
+}
+
+/// The bytes taken by the DAWG: a header per node and an offset per edge, with shared nodes
+/// counted once.
+fn dawg_size(
+    node: &words::Node,
+    seen: &mut std::collections::HashSet<*const words::Node>,
+) -> usize {
+    if !seen.insert(node) {
+        return 0;
+    }
+    4 + node
+        .children()
+        .map(|(_, child)| 4 + dawg_size(child, seen))
+        .sum::<usize>()
+}
+
+/// The fastest of a few runs of `f`.
+fn time<T>(runs: usize, mut f: impl FnMut() -> T) -> Duration {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let trie = TrieNode::build();
+    let (dawg, _) = words::load();
+
+    println!("load");
+    println!("  trie: {:>12?}", time(5, TrieNode::build));
+    println!("  dawg: {:>12?}", time(5, words::load));
+
+    println!("memory");
+    println!(
+        "  trie: {:>9} KiB",
+        trie.nodes() * std::mem::size_of::<TrieNode>() / 1024
+    );
+    println!(
+        "  dawg: {:>9} KiB",
+        dawg_size(dawg, &mut Default::default()) / 1024
+    );
+
+    println!("look up every word");
+    println!(
+        "  trie: {:>12?}",
+        time(5, || DICTIONARY
+            .lines()
+            .filter(|w| trie.contains(w))
+            .count())
+    );
+    println!(
+        "  dawg: {:>12?}",
+        time(5, || DICTIONARY
+            .lines()
+            .filter(|w| dawg.contains(w))
+            .count())
+    );
+}
@@ -0,0 +1,115 @@
+//! Compiles `src/dictionary` into a minimised DAWG that `words` embeds and walks in place.
+//!
+//! The output starts with the length of the longest word as a little-endian `u32`, followed by
+//! the nodes, root first. Each node is a little-endian `u32` whose low 26 bits say which letters
+//! have an edge and whose top bit says whether a word ends here, followed by one `u32` per edge,
+//! in letter order, giving the offset of the child from the start of this node. Children always
+//! come after all of their parents, so the offsets are positive.
+
+use std::collections::HashMap;
+
+const END: u32 = 1 << 31;
+
+#[derive(Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    is_end: bool,
+}
+
+/// A node of the minimised graph: whether a word ends here, and its edges to other unique nodes.
+type Signature = (bool, Vec<(u8, usize)>);
+
+fn minimise(
+    trie: &[TrieNode],
+    node: usize,
+    ids: &mut HashMap<Signature, usize>,
+    unique: &mut Vec<Signature>,
+) -> usize {
+    let mut children = trie[node]
+        .children
+        .iter()
+        .map(|&(letter, child)| (letter, minimise(trie, child, ids, unique)))
+        .collect::<Vec<_>>();
+    children.sort_unstable();
+
+    let signature = (trie[node].is_end, children);
+    if let Some(&id) = ids.get(&signature) {
+        return id;
+    }
+    let id = unique.len();
+    ids.insert(signature.clone(), id);
+    unique.push(signature);
+    id
+}
+
+fn postorder(unique: &[Signature], node: usize, seen: &mut [bool], order: &mut Vec<usize>) {
+    if seen[node] {
+        return;
+    }
+    seen[node] = true;
+    for &(_, child) in unique[node].1.iter() {
+        postorder(unique, child, seen, order);
+    }
+    order.push(node);
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/dictionary");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let dictionary = std::fs::read_to_string("src/dictionary").unwrap();
+
+    let mut trie = vec![TrieNode::default()];
+    let mut max_len = 0;
+    for word in dictionary.lines() {
+        let mut node = 0;
+        for letter in word.bytes() {
+            assert!(
+                letter.is_ascii_uppercase(),
+                "bad word in dictionary: {word:?}"
+            );
+            let letter = letter - b'A';
+            node = match trie[node].children.iter().find(|&&(l, _)| l == letter) {
+                Some(&(_, child)) => child,
+                None => {
+                    trie.push(TrieNode::default());
+                    let child = trie.len() - 1;
+                    trie[node].children.push((letter, child));
+                    child
+                }
+            };
+        }
+        trie[node].is_end = true;
+        max_len = max_len.max(word.len());
+    }
+
+    let mut unique = vec![];
+    let root = minimise(&trie, 0, &mut HashMap::new(), &mut unique);
+
+    let mut order = vec![];
+    postorder(&unique, root, &mut vec![false; unique.len()], &mut order);
+    order.reverse();
+
+    let mut offsets = vec![0; unique.len()];
+    let mut offset = 4;
+    for &node in order.iter() {
+        offsets[node] = offset;
+        offset += 4 + 4 * unique[node].1.len();
+    }
+
+    let mut out = Vec::with_capacity(offset);
+    out.extend((max_len as u32).to_le_bytes());
+    for &node in order.iter() {
+        let (is_end, children) = &unique[node];
+        let mask = children
+            .iter()
+            .fold(0, |mask, &(letter, _)| mask | 1 << letter);
+        out.extend((mask | if *is_end { END } else { 0 }).to_le_bytes());
+        for &(_, child) in children.iter() {
+            out.extend(((offsets[child] - offsets[node]) as u32).to_le_bytes());
+        }
+    }
+
+    let path = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("dictionary.dawg");
+    std::fs::write(path, out).unwrap();
+}
@@ -0,0 +1,75 @@
+#![no_main]
+
+#[path = "../../tests/reference/mod.rs"]
+mod reference;
+
+use spelltower::Tile;
+
+// Decodes `[rows, columns, tiles..., steps...]` into a tower and a walk over it, then checks
+// clearing and gravity against the reference implementation.
+libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+    let [n, m, rest @ ..] = data else {
+        return;
+    };
+    let (n, m) = ((*n % 8 + 1) as usize, (*m % 8 + 1) as usize);
+    if rest.len() < n * m {
+        return;
+    }
+    let (tiles, steps) = rest.split_at(n * m);
+
+    let tower = ndarray::Array2::from_shape_fn((n, m), |(i, j)| match tiles[i * m + j] % 32 {
+        26 => Tile::Blank,
+        27 => Tile::Wildcard,
+        28..=31 => Tile::Empty,
+        k => Tile::Letter {
+            letter: (b'A' + k) as char,
+            bonus: tiles[i * m + j] >= 128,
+        },
+    });
+
+    let mut path: Vec<(usize, usize, char)> = vec![];
+    for &step in steps {
+        let (i, j) = match path.last() {
+            None => ((step as usize / m) % n, step as usize % m),
+            Some(&(oi, oj, _)) => {
+                let (di, dj) = [
+                    (-1, -1),
+                    (-1, 0),
+                    (-1, 1),
+                    (0, -1),
+                    (0, 1),
+                    (1, -1),
+                    (1, 0),
+                    (1, 1),
+                ][step as usize % 8];
+                let (Some(i), Some(j)) = (oi.checked_add_signed(di), oj.checked_add_signed(dj))
+                else {
+                    continue;
+                };
+                (i, j)
+            }
+        };
+        if path.iter().any(|&(pi, pj, _)| (pi, pj) == (i, j)) {
+            continue;
+        }
+        let letter = match tower.get([i, j]) {
+            Some(Tile::Letter { letter, .. }) => *letter,
+            Some(Tile::Wildcard) => (b'A' + step % 26) as char,
+            _ => continue,
+        };
+        path.push((i, j, letter));
+    }
+
+    assert_eq!(
+        spelltower::deletable(&tower, &path),
+        reference::deletable(&tower, &path)
+    );
+
+    let mut actual = tower.clone();
+    spelltower::delete_path(&mut actual, &path);
+
+    let mut expected = tower.clone();
+    reference::delete_path(&mut expected, &path);
+
+    assert_eq!(actual, expected);
+});
@@ -0,0 +1,76 @@
+//! Finding where a played game lost points.
+
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+
+use crate::{hint::Hint, Path, Rules, Tower};
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct MoveAnalysis {
+    pub path: Path,
+    /// The points for this move alone.
+    pub score: usize,
+    /// The points for this move, plus what a greedy player would score afterwards.
+    pub value: usize,
+    /// The best move found from the same position, valued the same way.
+    pub best: Hint,
+    /// How many points were given up by not playing `best`.
+    pub regret: usize,
+}
+
+/// Compares every move of `solution` against the best of the `candidates` highest-scoring moves
+/// from the same position, each followed by a greedy continuation.
+pub fn analyze(
+    tower: &Tower,
+    root: &crate::words::Node,
+    rules: &Rules,
+    solution: &[impl AsRef<[(usize, usize, char)]>],
+    candidates: usize,
+) -> Vec<MoveAnalysis> {
+    let mut positions = vec![];
+    let mut position = tower.clone();
+    for path in solution {
+        positions.push((position.clone(), path.as_ref().to_vec()));
+        crate::delete_path(&mut position, path.as_ref());
+    }
+
+    positions
+        .into_par_iter()
+        .map(|(tower, path)| {
+            let score = crate::score_path(&tower, &path, rules);
+
+            let mut after = tower.clone();
+            crate::delete_path(&mut after, &path);
+            let rest = crate::solve_greedy(&after, root, rules);
+            let value = score + crate::score_solution(&after, &rest, rules);
+
+            let best = crate::hint::hints(&tower, root, rules, 1, candidates)
+                .into_iter()
+                .next()
+                .filter(|best| best.lookahead > value)
+                .unwrap_or_else(|| Hint {
+                    path: path.clone(),
+                    score,
+                    lookahead: value,
+                });
+
+            MoveAnalysis {
+                regret: best.lookahead - value,
+                best,
+                path,
+                score,
+                value,
+            }
+        })
+        .collect()
+}
+
+/// The index of the move in `moves` that gave up the most points, or `None` if none gave up any.
+/// Ties go to the latest such move.
+pub fn biggest_mistake(moves: &[MoveAnalysis]) -> Option<usize> {
+    moves
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.regret > 0)
+        .max_by_key(|(_, m)| m.regret)
+        .map(|(k, _)| k)
+}
@@ -1,20 +1,34 @@
+use crate::{Path, Solution};
+
+/// What the annealer minimises, and the cost at which it can stop early.
 pub struct Coster {
-    pub cost: for<'a> fn(tower: &'a ndarray::Array2<char>, param: &[Vec<(usize, usize)>]) -> f64,
+    pub cost: for<'a> fn(tower: &'a crate::Tower, param: &[Path], rules: &crate::Rules) -> f64,
     pub target: f64,
 }
 
+/// Searches for a solution to a tower by simulated annealing.
 pub struct Annealer<'a> {
-    tower: &'a ndarray::Array2<char>,
+    tower: &'a crate::Tower,
     root: &'a crate::words::Node,
+    rules: crate::Rules,
     allow_leftovers: bool,
     rng: std::cell::RefCell<rand_xoshiro::Xoshiro256PlusPlus>,
     coster: &'static Coster,
+    stop: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
 }
 
+/// Returned by the annealer once its stop flag has been set.
+#[derive(thiserror::Error, Debug)]
+#[error("search stopped")]
+pub struct Stopped;
+
 impl<'a> Annealer<'a> {
+    /// An annealer for `tower` minimising `coster`. Unless `allow_leftovers` is set, solutions
+    /// that leave tiles on the tower cost the most possible.
     pub fn new(
-        tower: &'a ndarray::Array2<char>,
+        tower: &'a crate::Tower,
         root: &'a crate::words::Node,
+        rules: crate::Rules,
         allow_leftovers: bool,
         rng: rand_xoshiro::Xoshiro256PlusPlus,
         coster: &'static Coster,
@@ -22,35 +36,43 @@ impl<'a> Annealer<'a> {
         Self {
             tower,
             root,
+            rules,
             allow_leftovers,
             rng: std::cell::RefCell::new(rng),
             coster,
+            stop: None,
         }
     }
+
+    /// Makes annealing fail with [`Stopped`] as soon as `stop` is set.
+    pub fn with_stop(mut self, stop: std::sync::Arc<std::sync::atomic::AtomicBool>) -> Self {
+        self.stop = Some(stop);
+        self
+    }
 }
 
 impl<'a> argmin::core::CostFunction for Annealer<'a> {
-    type Param = Vec<Vec<(usize, usize)>>;
+    type Param = Solution;
     type Output = f64;
 
     fn cost(&self, param: &Self::Param) -> Result<Self::Output, argmin::core::Error> {
         if !self.allow_leftovers {
             let mut tower = self.tower.clone();
             for path in param {
-                super::delete_path(&mut tower, path);
+                crate::delete_path(&mut tower, path);
             }
-            if tower.len() - tower.iter().filter(|&&x| x == '\0').count() != 0 {
-                return Ok(std::f64::MAX);
+            if !tower.iter().all(|x| x.is_empty()) {
+                return Ok(f64::MAX);
             }
         }
 
-        Ok((self.coster.cost)(&self.tower, &param))
+        Ok((self.coster.cost)(self.tower, param, &self.rules))
     }
 }
 
 impl<'a> argmin::solver::simulatedannealing::Anneal for Annealer<'a> {
-    type Param = Vec<Vec<(usize, usize)>>;
-    type Output = Vec<Vec<(usize, usize)>>;
+    type Param = Solution;
+    type Output = Solution;
     type Float = f64;
 
     fn anneal(
@@ -58,48 +80,61 @@ impl<'a> argmin::solver::simulatedannealing::Anneal for Annealer<'a> {
         param: &Self::Param,
         temp: Self::Float,
     ) -> Result<Self::Output, anyhow::Error> {
+        if self
+            .stop
+            .as_ref()
+            .is_some_and(|stop| stop.load(std::sync::atomic::Ordering::Relaxed))
+        {
+            return Err(Stopped.into());
+        }
+
         let mut rng = self.rng.borrow_mut();
         let mut solution = param.to_vec();
         for _ in 0..(temp.floor() as u64 + 1) {
-            super::nudge_solution(&self.tower, &self.root, &mut solution, &mut *rng);
+            crate::nudge_solution(self.tower, self.root, &self.rules, &mut solution, &mut *rng);
         }
         Ok(solution)
     }
 }
 
-fn longest_word(solution: &[impl AsRef<[(usize, usize)]>]) -> usize {
+fn longest_word(solution: &[impl AsRef<[(usize, usize, char)]>]) -> usize {
     solution.iter().map(|v| v.as_ref().len()).max().unwrap_or(0)
 }
 
 fn best_word_score(
-    tower: &ndarray::Array2<char>,
-    solution: &[impl AsRef<[(usize, usize)]>],
+    tower: &crate::Tower,
+    solution: &[impl AsRef<[(usize, usize, char)]>],
+    rules: &crate::Rules,
 ) -> usize {
     let mut tower = tower.clone();
     let mut best_score = 0;
     for path in solution {
-        best_score = best_score.max(super::score_path(&tower, path.as_ref()));
-        super::delete_path(&mut tower, path.as_ref());
+        best_score = best_score.max(crate::score_path(&tower, path.as_ref(), rules));
+        crate::delete_path(&mut tower, path.as_ref());
     }
     best_score
 }
 
+/// Prefers solutions with a longer longest word.
 pub const LONGEST_WORD: Coster = Coster {
-    target: std::f64::NEG_INFINITY,
-    cost: |_tower, solution| -(longest_word(solution) as f64),
+    target: f64::NEG_INFINITY,
+    cost: |_tower, solution, _rules| -(longest_word(solution) as f64),
 };
 
+/// Prefers solutions scoring more points.
 pub const TOTAL_SCORE: Coster = Coster {
-    target: std::f64::NEG_INFINITY,
-    cost: |tower, solution| -(super::score_solution(tower, solution) as f64),
+    target: f64::NEG_INFINITY,
+    cost: |tower, solution, rules| -(crate::score_solution(tower, solution, rules) as f64),
 };
 
+/// Prefers solutions whose best move scores more points.
 pub const BEST_WORD: Coster = Coster {
-    target: std::f64::NEG_INFINITY,
-    cost: |tower, solution| -(best_word_score(tower, solution) as f64),
+    target: f64::NEG_INFINITY,
+    cost: |tower, solution, rules| -(best_word_score(tower, solution, rules) as f64),
 };
 
+/// Prefers solutions with fewer moves.
 pub const FEWEST_WORDS: Coster = Coster {
     target: 1.0,
-    cost: |_tower, solution| solution.len() as f64,
+    cost: |_tower, solution, _rules| solution.len() as f64,
 };
@@ -0,0 +1,85 @@
+//! Comparing two solutions to the same puzzle.
+
+use std::fmt::Write as _;
+
+use crate::{Rules, Solution, Style, Tower};
+
+/// Draws the moves `a` and `b` have in common, then both towers at the first move they differ,
+/// then each move of both side by side with its running total, marking the moves after they
+/// part with `*`. `names` heads the columns for `a` and `b`.
+pub fn compare(
+    tower: &Tower,
+    a: &Solution,
+    b: &Solution,
+    names: [&str; 2],
+    rules: &Rules,
+    style: &Style,
+) -> String {
+    let mut out = String::new();
+    let common = a.iter().zip(b.iter()).take_while(|(a, b)| a == b).count();
+
+    let word = |path: &[(usize, usize, char)]| {
+        path.iter()
+            .map(|&(_, _, letter)| letter)
+            .collect::<String>()
+    };
+
+    let mut tower_at = tower.clone();
+    for path in a[..common].iter() {
+        writeln!(
+            out,
+            "{}",
+            crate::pretty_tower(&tower_at, path, rules, style)
+        )
+        .unwrap();
+        crate::delete_path(&mut tower_at, path);
+    }
+    writeln!(out, "{common} moves in common").unwrap();
+
+    if common < a.len().max(b.len()) {
+        let empty = vec![];
+        let left = crate::pretty_tower(&tower_at, a.get(common).unwrap_or(&empty), rules, style);
+        let right = crate::pretty_tower(&tower_at, b.get(common).unwrap_or(&empty), rules, style);
+        writeln!(out, "first difference at move {}:", common + 1).unwrap();
+        for (l, r) in left.lines().zip(right.lines()) {
+            writeln!(out, "{l}  {r}").unwrap();
+        }
+    }
+
+    writeln!(out).unwrap();
+    let scores_a = crate::cumulative_scores(tower, a, rules);
+    let scores_b = crate::cumulative_scores(tower, b, rules);
+    writeln!(
+        out,
+        "{:>4}  {:<15} {:>6}  {:<15} {:>6}",
+        "move", names[0], "total", names[1], "total"
+    )
+    .unwrap();
+    for k in 0..a.len().max(b.len()) {
+        let column = |solution: &Solution, scores: &[usize]| match solution.get(k) {
+            Some(path) => format!("{:<15} {:>6}", word(path), scores[k]),
+            None => format!("{:<15} {:>6}", "", ""),
+        };
+        writeln!(
+            out,
+            "{:>4}{} {}  {}",
+            k + 1,
+            if k < common { ' ' } else { '*' },
+            column(a, &scores_a),
+            column(b, &scores_b)
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "{:>4}  {:<15} {:>6}  {:<15} {:>6}",
+        "",
+        "TOTAL SCORE",
+        crate::score_solution(tower, a, rules),
+        "TOTAL SCORE",
+        crate::score_solution(tower, b, rules)
+    )
+    .unwrap();
+
+    out
+}
@@ -0,0 +1,578 @@
+//! A line-oriented protocol for driving the solver from other programs, in the spirit of UCI.
+//!
+//! Cells are written `ROW,COL`, with `:LETTER` appended for wildcards, and joined with `-` into a
+//! path. Commands answer with `ok` last unless shown otherwise below, or with `error <message>`.
+//!
+//! ```text
+//! spelltower                   id name spelltower <version>, then spelltowerok
+//! isready                      readyok
+//! load                         followed by a puzzle in Puzzmo text format, then a line `end`
+//! loadfile <file>              loads a puzzle from a file
+//! show                         the current tower in Puzzmo text format
+//! score                        score <points so far, with the end bonuses once no moves are left>
+//! moves                        move <word> <score> <path>, for every legal move
+//! hint [n]                     hint <word> <score> <lookahead> <path>, for the n best moves
+//! play <path>                  played <word> <score>; wildcard letters are chosen if missing, and
+//!                              the path may also be in move notation, e.g. C4-D5=E-E5
+//! undo, redo
+//! go [movetime <ms>] [iterations <n>] [coster <name>] [leftovers]
+//!                              searches in the background, printing
+//!                              info iter <n> score <points> solution <word> <path> ...
+//!                              whenever it finds a better solution, and finally
+//!                              bestsolution score <points> solution <word> <path> ...
+//!                              or error no moves if there is nothing to play
+//! stop                         stops the search
+//! quit
+//! ```
+
+use clap::ValueEnum as _;
+use rand::SeedableRng as _;
+use spelltower::{annealers, game, hint, notation, puzzle, words, Rules, Solution, Tile, Tower};
+use std::io::BufRead;
+use std::sync::{atomic::AtomicBool, Arc, Mutex};
+
+type State = argmin::core::IterState<Solution, (), (), (), (), f64>;
+
+/// Where the answers go, shared with the search running in the background.
+type Output = Arc<Mutex<dyn std::io::Write + Send>>;
+
+/// Writes a line of `format!` arguments to an [`Output`].
+macro_rules! say {
+    ($output:expr, $($arg:tt)*) => {
+        writeln!($output.lock().unwrap(), $($arg)*)
+    };
+}
+
+fn path_token(tower: &Tower, path: &[(usize, usize, char)]) -> String {
+    path.iter()
+        .map(|&(i, j, letter)| {
+            if tower[[i, j]] == Tile::Wildcard {
+                format!("{i},{j}:{letter}")
+            } else {
+                format!("{i},{j}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn word(path: &[(usize, usize, char)]) -> String {
+    path.iter().map(|&(_, _, letter)| letter).collect()
+}
+
+fn solution_tokens(tower: &Tower, solution: &Solution) -> String {
+    let mut tower = tower.clone();
+    solution
+        .iter()
+        .map(|path| {
+            let token = format!("{} {}", word(path), path_token(&tower, path));
+            spelltower::delete_path(&mut tower, path);
+            token
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads a cell as `ROW,COL`, counting from 0.
+fn parse_cell(s: &str) -> anyhow::Result<(usize, usize)> {
+    let (i, j) = s
+        .split_once(',')
+        .ok_or_else(|| anyhow::anyhow!("{s} is not a cell"))?;
+    Ok((i.trim().parse()?, j.trim().parse()?))
+}
+
+/// Parses a path, choosing the highest-scoring letters for wildcards that don't say.
+fn parse_path(
+    tower: &Tower,
+    root: &words::Node,
+    rules: &Rules,
+    s: &str,
+) -> anyhow::Result<spelltower::Path> {
+    if !s.contains(',') {
+        return Ok(notation::parse_path(tower, root, rules, s)?);
+    }
+
+    let mut cells = vec![];
+    let mut letters = vec![];
+    for cell in s.split('-') {
+        let (cell, letter) = match cell.split_once(':') {
+            Some((cell, letter)) => (cell, letter.chars().next()),
+            None => (cell, None),
+        };
+        cells.push(parse_cell(cell)?);
+        letters.push(letter.map(|c| c.to_ascii_uppercase()));
+    }
+
+    game::read_word_as(tower, root, rules, &cells, &letters)
+        .ok_or_else(|| anyhow::anyhow!("{s} is not a word"))
+}
+
+struct InfoObserver {
+    tower: Tower,
+    rules: Rules,
+    best: Arc<Mutex<Solution>>,
+    output: Output,
+}
+
+impl argmin::core::observers::Observe<State> for InfoObserver {
+    fn observe_iter(&mut self, state: &State, _kv: &argmin::core::KV) -> anyhow::Result<()> {
+        let Some(solution) = state.best_param.as_ref() else {
+            return Ok(());
+        };
+        say!(
+            self.output,
+            "info iter {} score {} solution {}",
+            state.iter,
+            spelltower::score_solution(&self.tower, solution, &self.rules),
+            solution_tokens(&self.tower, solution)
+        )?;
+        *self.best.lock().unwrap() = solution.clone();
+        Ok(())
+    }
+}
+
+struct Limits {
+    movetime: Option<std::time::Duration>,
+    iterations: Option<u64>,
+    coster: crate::Coster,
+    allow_leftovers: bool,
+}
+
+fn parse_limits(args: &[&str]) -> anyhow::Result<Limits> {
+    let mut limits = Limits {
+        movetime: None,
+        iterations: None,
+        coster: crate::Coster::TotalScore,
+        allow_leftovers: false,
+    };
+
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| anyhow::anyhow!("{arg} needs a value"))
+        };
+        match arg {
+            "movetime" => {
+                limits.movetime = Some(std::time::Duration::from_millis(value()?.parse()?))
+            }
+            "iterations" => limits.iterations = Some(value()?.parse()?),
+            "coster" => {
+                limits.coster =
+                    crate::Coster::from_str(value()?, true).map_err(|e| anyhow::anyhow!(e))?
+            }
+            "leftovers" => limits.allow_leftovers = true,
+            _ => anyhow::bail!("unknown limit {arg}"),
+        }
+    }
+
+    Ok(limits)
+}
+
+fn search(
+    tower: Tower,
+    root: &words::Node,
+    rules: Rules,
+    limits: Limits,
+    stop: Arc<AtomicBool>,
+    output: Output,
+) -> anyhow::Result<()> {
+    // The greedy solution is the starting point, so it's what we answer with if we're stopped
+    // before the annealer gets anywhere.
+    let greedy = spelltower::solve_greedy(&tower, root, &rules);
+    anyhow::ensure!(!greedy.is_empty(), "no moves");
+    let best = Arc::new(Mutex::new(greedy.clone()));
+    let coster = limits.coster.as_coster();
+
+    let mut executor = argmin::core::Executor::new(
+        annealers::Annealer::new(
+            &tower,
+            root,
+            rules,
+            limits.allow_leftovers,
+            rand_xoshiro::Xoshiro256PlusPlus::from_entropy(),
+            coster,
+        )
+        .with_stop(stop),
+        argmin::solver::simulatedannealing::SimulatedAnnealing::new(1000.0)?
+            .with_reannealing_fixed(5000),
+    )
+    .configure(|state| {
+        let state = state.param(greedy).target_cost(coster.target);
+        match limits.iterations {
+            Some(n) => state.max_iters(n),
+            None => state,
+        }
+    })
+    .ctrlc(false)
+    .add_observer(
+        InfoObserver {
+            tower: tower.clone(),
+            rules,
+            best: best.clone(),
+            output: output.clone(),
+        },
+        argmin::core::observers::ObserverMode::NewBest,
+    );
+    if let Some(movetime) = limits.movetime {
+        executor = executor.timeout(movetime);
+    }
+
+    let solution = match executor.run() {
+        Ok(res) => res
+            .state
+            .best_param
+            .unwrap_or_else(|| best.lock().unwrap().clone()),
+        Err(e) if e.is::<annealers::Stopped>() => best.lock().unwrap().clone(),
+        Err(e) => return Err(e),
+    };
+
+    say!(
+        output,
+        "bestsolution score {} solution {}",
+        spelltower::score_solution(&tower, &solution, &rules),
+        solution_tokens(&tower, &solution)
+    )?;
+    Ok(())
+}
+
+type Search<'scope> = (Arc<AtomicBool>, std::thread::ScopedJoinHandle<'scope, ()>);
+
+fn stop_search(searching: &mut Option<Search>, output: &Output) -> std::io::Result<()> {
+    if let Some((stop, handle)) = searching.take() {
+        stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        if handle.join().is_err() {
+            say!(output, "error the search failed")?;
+        }
+    }
+    Ok(())
+}
+
+pub fn run(root: &words::Node, rules: Rules) -> anyhow::Result<()> {
+    run_on(
+        root,
+        rules,
+        std::io::stdin().lock(),
+        Arc::new(Mutex::new(std::io::stdout())),
+    )
+}
+
+/// Like [`run`], but reads the commands from `input` and answers on `output`.
+fn run_on(
+    root: &words::Node,
+    rules: Rules,
+    input: impl BufRead,
+    output: Output,
+) -> anyhow::Result<()> {
+    let mut lines = input.lines();
+
+    let mut puzzle = puzzle::Puzzle {
+        title: String::new(),
+        tower: Tower::default((0, 0)),
+        metadata: vec![],
+        line_endings: vec![],
+        trailing_newline: true,
+    };
+    let mut game = game::Game::new(puzzle.tower.clone(), root, rules);
+
+    std::thread::scope(|scope| -> anyhow::Result<()> {
+        let mut searching = None;
+
+        while let Some(line) = lines.next() {
+            let line = line?;
+            let args = line.split_whitespace().collect::<Vec<_>>();
+            let Some((&command, args)) = args.split_first() else {
+                continue;
+            };
+
+            let mut handle = || -> anyhow::Result<bool> {
+                match command {
+                    "spelltower" => {
+                        say!(output, "id name spelltower {}", env!("CARGO_PKG_VERSION"))?;
+                        say!(output, "spelltowerok")?;
+                        return Ok(false);
+                    }
+                    "isready" => {
+                        say!(output, "readyok")?;
+                        return Ok(false);
+                    }
+                    "load" | "loadfile" => {
+                        let text = if command == "loadfile" {
+                            std::fs::read_to_string(args.join(" "))?
+                        } else {
+                            let mut text = String::new();
+                            for line in lines.by_ref() {
+                                let line = line?;
+                                if line == "end" {
+                                    break;
+                                }
+                                text.push_str(&line);
+                                text.push('\n');
+                            }
+                            text
+                        };
+                        stop_search(&mut searching, &output)?;
+                        puzzle = puzzle::parse(&text)?;
+                        game = game::Game::new(puzzle.tower.clone(), root, rules);
+                    }
+                    "show" => {
+                        puzzle.tower = game.tower().clone();
+                        write!(output.lock().unwrap(), "{puzzle}")?;
+                    }
+                    "score" => say!(output, "score {}", game.total_score())?,
+                    "moves" => {
+                        for path in game.legal_moves() {
+                            say!(
+                                output,
+                                "move {} {} {}",
+                                word(path),
+                                spelltower::score_path(game.tower(), path, &rules),
+                                path_token(game.tower(), path)
+                            )?;
+                        }
+                    }
+                    "hint" => {
+                        let n = args.first().map(|n| n.parse()).transpose()?.unwrap_or(1);
+                        for hint in hint::hints(game.tower(), root, &rules, n, n.max(20)) {
+                            say!(
+                                output,
+                                "hint {} {} {} {}",
+                                word(&hint.path),
+                                hint.score,
+                                hint.lookahead,
+                                path_token(game.tower(), &hint.path)
+                            )?;
+                        }
+                    }
+                    "play" => {
+                        let s = args
+                            .first()
+                            .ok_or_else(|| anyhow::anyhow!("play needs a path"))?;
+                        let path = parse_path(game.tower(), root, &rules, s)?;
+                        let m = game.play(&path)?;
+                        say!(output, "played {} {}", word(&m.path), m.score)?;
+                    }
+                    "undo" => {
+                        game.undo()
+                            .ok_or_else(|| anyhow::anyhow!("nothing to undo"))?;
+                    }
+                    "redo" => {
+                        game.redo()
+                            .ok_or_else(|| anyhow::anyhow!("nothing to redo"))?;
+                    }
+                    "go" => {
+                        let limits = parse_limits(args)?;
+                        stop_search(&mut searching, &output)?;
+                        let stop = Arc::new(AtomicBool::new(false));
+                        let tower = game.tower().clone();
+                        let handle = scope.spawn({
+                            let stop = stop.clone();
+                            let output = output.clone();
+                            // Errors are reported as soon as they happen, rather than when the
+                            // search is next stopped.
+                            move || {
+                                if let Err(e) =
+                                    search(tower, root, rules, limits, stop, output.clone())
+                                {
+                                    // There is nowhere left to report a failure to write.
+                                    let _ = say!(output, "error {e}");
+                                }
+                            }
+                        });
+                        searching = Some((stop, handle));
+                        return Ok(false);
+                    }
+                    "stop" => {
+                        stop_search(&mut searching, &output)?;
+                        return Ok(false);
+                    }
+                    "quit" => return Ok(true),
+                    _ => anyhow::bail!("unknown command {command}"),
+                }
+                say!(output, "ok")?;
+                Ok(false)
+            };
+
+            match handle() {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(e) => say!(output, "error {e}")?,
+            }
+        }
+
+        stop_search(&mut searching, &output)?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tower(rows: &str) -> Tower {
+        let width = rows.lines().next().unwrap().len();
+        let height = rows.lines().count();
+        puzzle::parse(&format!("SpellTower\n{width}x{height}\n{rows}"))
+            .unwrap()
+            .tower
+    }
+
+    fn transcript(input: &str) -> String {
+        let (root, _) = words::load();
+        let output = Arc::new(Mutex::new(vec![]));
+        run_on(root, Rules::default(), input.as_bytes(), output.clone()).unwrap();
+        let output = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+        // How often the search finds something better depends on the random moves it tries.
+        output
+            .lines()
+            .filter(|line| !line.starts_with("info "))
+            .map(|line| format!("{line}\n"))
+            .collect()
+    }
+
+    #[test]
+    fn parses_cells() {
+        assert_eq!(parse_cell("3,14").unwrap(), (3, 14));
+        assert_eq!(parse_cell(" 3, 14 ").unwrap(), (3, 14));
+        assert_eq!(parse_cell("3").unwrap_err().to_string(), "3 is not a cell");
+        assert!(parse_cell("3,x").is_err());
+        assert!(parse_cell("-1,0").is_err());
+    }
+
+    #[test]
+    fn parses_paths() {
+        let (root, _) = words::load();
+        let rules = Rules::default();
+        let cat = tower("CAT");
+        assert_eq!(
+            parse_path(&cat, root, &rules, "0,0-0,1-0,2").unwrap(),
+            [(0, 0, 'C'), (0, 1, 'A'), (0, 2, 'T')]
+        );
+        assert_eq!(
+            parse_path(&cat, root, &rules, "0,2-0,1-0,0")
+                .unwrap_err()
+                .to_string(),
+            "0,2-0,1-0,0 is not a word"
+        );
+        assert!(parse_path(&cat, root, &rules, "0,0-0,1-0").is_err());
+    }
+
+    #[test]
+    fn parses_paths_with_wildcards() {
+        let (root, _) = words::load();
+        let rules = Rules::default();
+        let c_t = tower("C?T");
+        assert_eq!(
+            word(&parse_path(&c_t, root, &rules, "0,0-0,1:U-0,2").unwrap()),
+            "CUT"
+        );
+        assert_eq!(
+            word(&parse_path(&c_t, root, &rules, "0,0-0,1:o-0,2").unwrap()),
+            "COT"
+        );
+        assert!(parse_path(&c_t, root, &rules, "0,0-0,1:X-0,2").is_err());
+
+        // The wildcard is chosen when left out.
+        let path = parse_path(&c_t, root, &rules, "0,0-0,1-0,2").unwrap();
+        assert!(root.contains(&word(&path)));
+    }
+
+    #[test]
+    fn parses_paths_in_move_notation() {
+        let (root, _) = words::load();
+        let rules = Rules::default();
+        assert_eq!(
+            word(&parse_path(&tower("C?T"), root, &rules, "A1-B1=U-C1").unwrap()),
+            "CUT"
+        );
+        assert_eq!(
+            parse_path(&tower("CAT"), root, &rules, "C1-B1-A1")
+                .unwrap_err()
+                .to_string(),
+            "C1-B1-A1 does not spell a word"
+        );
+    }
+
+    #[test]
+    fn parses_limits() {
+        let limits = parse_limits(&[]).unwrap();
+        assert_eq!(limits.movetime, None);
+        assert_eq!(limits.iterations, None);
+        assert!(matches!(limits.coster, crate::Coster::TotalScore));
+        assert!(!limits.allow_leftovers);
+
+        let limits = parse_limits(&["movetime", "250", "iterations", "7", "leftovers"]).unwrap();
+        assert_eq!(limits.movetime, Some(std::time::Duration::from_millis(250)));
+        assert_eq!(limits.iterations, Some(7));
+        assert!(limits.allow_leftovers);
+    }
+
+    #[test]
+    fn rejects_bad_limits() {
+        let error = |args: &[&str]| parse_limits(args).err().unwrap().to_string();
+        assert_eq!(error(&["movetime"]), "movetime needs a value");
+        assert_eq!(
+            error(&["iterations", "7", "coster"]),
+            "coster needs a value"
+        );
+        assert_eq!(error(&["depth", "3"]), "unknown limit depth");
+        assert!(error(&["coster", "nonsense"]).contains("nonsense"));
+        assert!(parse_limits(&["iterations", "many"]).is_err());
+    }
+
+    #[test]
+    fn answers_commands() {
+        let input = "\
+            spelltower\n\
+            isready\n\
+            load\n\
+            SpellTower\n\
+            3x1\n\
+            CAT\n\
+            end\n\
+            moves\n\
+            play 0,0-0,1-0,2\n\
+            undo\n\
+            undo\n\
+            score\n\
+            play 0,0-0,2\n\
+            bogus\n\
+            go iterations 1\n\
+            stop\n\
+            go depth 3\n\
+            play A1-B1-C1\n\
+            redo\n\
+            score\n\
+            go\n\
+            stop\n\
+            quit\n\
+            isready\n";
+        let expected = format!(
+            "\
+            id name spelltower {}\n\
+            spelltowerok\n\
+            readyok\n\
+            ok\n\
+            move CAT 21 0,0-0,1-0,2\n\
+            ok\n\
+            played CAT 21\n\
+            ok\n\
+            ok\n\
+            error nothing to undo\n\
+            score 0\n\
+            ok\n\
+            error 0,0-0,2 is not a word\n\
+            error unknown command bogus\n\
+            bestsolution score 2021 solution CAT 0,0-0,1-0,2\n\
+            error unknown limit depth\n\
+            played CAT 21\n\
+            ok\n\
+            error nothing to redo\n\
+            score 2021\n\
+            ok\n\
+            error no moves\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(transcript(input), expected);
+    }
+}
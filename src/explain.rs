@@ -0,0 +1,115 @@
+//! Breaking a move's score down into where the points came from.
+
+use crate::{ClearReason, Rules, Tile, Tower};
+
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+pub struct Cell {
+    pub cell: (usize, usize),
+    pub tile: Tile,
+    /// The letter the tile was played as, if it is part of the word.
+    pub letter: Option<char>,
+    pub points: usize,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Breakdown {
+    pub word: String,
+    /// The tiles of the word, in order.
+    pub letters: Vec<Cell>,
+    /// Other tiles cleared by a word of 5 or more letters.
+    pub adjacent: Vec<Cell>,
+    /// Other tiles cleared because the word used a J, Q, X or Z in their row.
+    pub rare_letter_rows: Vec<Cell>,
+    /// Blanks cleared by being next to the word.
+    pub blanks: Vec<Cell>,
+    pub letter_sum: usize,
+    pub length_multiplier: usize,
+    pub bonus_multiplier: usize,
+    pub score: usize,
+}
+
+/// Breaks the score of `path` on `tower` down into the points for each cleared tile, the length
+/// of the word and the bonus multiplier.
+pub fn explain(tower: &Tower, path: &[(usize, usize, char)], rules: &Rules) -> Breakdown {
+    let cell = |(i, j), letter| Cell {
+        cell: (i, j),
+        tile: tower[[i, j]],
+        letter,
+        points: crate::score_cell(tower, path, rules, (i, j)),
+    };
+
+    let mut reasons = crate::clear_reasons(tower, path)
+        .into_iter()
+        .collect::<Vec<_>>();
+    reasons.sort_unstable_by_key(|&(cell, _)| cell);
+
+    let extra = |reason| {
+        reasons
+            .iter()
+            .filter(|&&(_, r)| r == reason)
+            .map(|&(c, _)| cell(c, None))
+            .collect::<Vec<_>>()
+    };
+
+    let letters = path
+        .iter()
+        .map(|&(i, j, letter)| cell((i, j), Some(letter)))
+        .collect::<Vec<_>>();
+    let adjacent = extra(ClearReason::Adjacent);
+    let rare_letter_rows = extra(ClearReason::RareLetterRow);
+    let blanks = extra(ClearReason::Blank);
+
+    let letter_sum = [&letters, &adjacent, &rare_letter_rows, &blanks]
+        .into_iter()
+        .flatten()
+        .map(|c| c.points)
+        .sum::<usize>();
+    let length_multiplier = path.len();
+    let bonus_multiplier = crate::bonus_multiplier(tower, path);
+
+    Breakdown {
+        word: path.iter().map(|&(_, _, letter)| letter).collect(),
+        letters,
+        adjacent,
+        rare_letter_rows,
+        blanks,
+        letter_sum,
+        length_multiplier,
+        bonus_multiplier,
+        score: letter_sum * length_multiplier * bonus_multiplier,
+    }
+}
+
+impl std::fmt::Display for Breakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (label, cells) in [
+            ("letters", &self.letters),
+            ("5+ adjacent", &self.adjacent),
+            ("J/Q/X/Z row", &self.rare_letter_rows),
+            ("blanks", &self.blanks),
+        ] {
+            if cells.is_empty() {
+                continue;
+            }
+            let values = cells
+                .iter()
+                .map(|c| {
+                    let letter = c.letter.unwrap_or_else(|| char::from(c.tile));
+                    if c.tile.is_bonus() {
+                        format!("{letter}*={}", c.points)
+                    } else {
+                        format!("{letter}={}", c.points)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            let sum = cells.iter().map(|c| c.points).sum::<usize>();
+            writeln!(f, "{label:>12}: {values} ({sum})")?;
+        }
+        write!(
+            f,
+            "{:>12}: {} × {} (length) × {} (bonus tiles + 1) = {}",
+            self.word, self.letter_sum, self.length_multiplier, self.bonus_multiplier, self.score
+        )
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{Path, Rules, Solution, Tower};
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("empty path")]
+    EmptyPath,
+
+    #[error("({0}, {1}) is not on the tower")]
+    OutOfBounds(usize, usize),
+
+    #[error("({0}, {1}) is used more than once")]
+    Reused(usize, usize),
+
+    #[error("({0}, {1}) is not next to the previous tile")]
+    NotAdjacent(usize, usize),
+
+    #[error("({0}, {1}) cannot be played as {2:?}")]
+    WrongLetter(usize, usize, char),
+
+    #[error("{0:?} is not a word")]
+    NotAWord(String),
+}
+
+/// Checks that `path` spells a word in `root` using adjacent, unused tiles of `tower`.
+pub fn validate_path(
+    tower: &Tower,
+    root: &crate::words::Node,
+    path: &[(usize, usize, char)],
+) -> Result<(), Error> {
+    if path.is_empty() {
+        return Err(Error::EmptyPath);
+    }
+
+    let mut node = Some(root);
+    for (k, &(i, j, letter)) in path.iter().enumerate() {
+        let tile = *tower.get([i, j]).ok_or(Error::OutOfBounds(i, j))?;
+
+        if path[..k].iter().any(|&(pi, pj, _)| (pi, pj) == (i, j)) {
+            return Err(Error::Reused(i, j));
+        }
+
+        if let Some(&(pi, pj, _)) = k.checked_sub(1).map(|k| &path[k]) {
+            if pi.abs_diff(i) > 1 || pj.abs_diff(j) > 1 {
+                return Err(Error::NotAdjacent(i, j));
+            }
+        }
+
+        if !tile.can_be(letter) {
+            return Err(Error::WrongLetter(i, j, letter));
+        }
+
+        node = node.and_then(|node| node.get(letter));
+    }
+
+    if !node.is_some_and(|node| node.is_end()) {
+        return Err(Error::NotAWord(
+            path.iter().map(|&(_, _, letter)| letter).collect(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Every way of reading `cells` as the start of a word in `root`, trying every letter for
+/// wildcards, with the dictionary node each reading ends at. Empty if `cells` cannot begin a word.
+pub fn readings<'r>(
+    tower: &Tower,
+    root: &'r crate::words::Node,
+    cells: &[(usize, usize)],
+) -> Vec<(Path, &'r crate::words::Node)> {
+    let mut readings = vec![(vec![], root)];
+    for &(i, j) in cells {
+        let Some(&tile) = tower.get([i, j]) else {
+            return vec![];
+        };
+        readings = readings
+            .into_iter()
+            .flat_map(|(path, node)| {
+                node.children()
+                    .filter(|&(letter, _)| tile.can_be(letter))
+                    .map(move |(letter, child)| {
+                        let mut path = path.clone();
+                        path.push((i, j, letter));
+                        (path, child)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+    readings
+}
+
+/// The highest-scoring way of reading `cells` as a whole word, if there is one.
+pub fn read_word(
+    tower: &Tower,
+    root: &crate::words::Node,
+    rules: &Rules,
+    cells: &[(usize, usize)],
+) -> Option<Path> {
+    read_word_as(tower, root, rules, cells, &[])
+}
+
+/// Like [`read_word`], but only reading the tiles that `letters` has a letter for as that letter.
+pub fn read_word_as(
+    tower: &Tower,
+    root: &crate::words::Node,
+    rules: &Rules,
+    cells: &[(usize, usize)],
+    letters: &[Option<char>],
+) -> Option<Path> {
+    readings(tower, root, cells)
+        .into_iter()
+        .filter(|(path, node)| {
+            node.is_end()
+                && path
+                    .iter()
+                    .zip(letters.iter())
+                    .all(|(&(_, _, c), l)| l.is_none_or(|l| l == c))
+        })
+        .map(|(path, _)| path)
+        .max_by_key(|path| crate::score_path(tower, path, rules))
+}
+
+/// A move that has been played.
+#[derive(Clone, Debug)]
+pub struct Move {
+    pub path: Path,
+
+    /// The points scored by this move alone.
+    pub score: usize,
+
+    /// The tiles this move cleared and the tiles that fell afterwards.
+    pub diff: crate::Diff,
+}
+
+/// A game in progress.
+pub struct Game<'a> {
+    root: &'a crate::words::Node,
+    rules: Rules,
+    tower: Tower,
+    history: Vec<Move>,
+    before: Vec<Tower>,
+    undone: Vec<Move>,
+    /// The moves that can be played on `tower`, found when first asked for.
+    legal_moves: std::cell::OnceCell<Vec<Path>>,
+}
+
+impl<'a> Game<'a> {
+    /// Starts a game on `tower`, checking words against the dictionary rooted at `root`.
+    pub fn new(tower: Tower, root: &'a crate::words::Node, rules: Rules) -> Self {
+        Self {
+            root,
+            rules,
+            tower,
+            history: vec![],
+            before: vec![],
+            undone: vec![],
+            legal_moves: std::cell::OnceCell::new(),
+        }
+    }
+
+    /// The tower as it currently stands.
+    pub fn tower(&self) -> &Tower {
+        &self.tower
+    }
+
+    /// The rules the game is scored by.
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// The moves played so far, oldest first.
+    pub fn history(&self) -> &[Move] {
+        &self.history
+    }
+
+    /// The paths of the moves played so far, oldest first.
+    pub fn solution(&self) -> Solution {
+        self.history.iter().map(|m| m.path.clone()).collect()
+    }
+
+    /// The points scored by the moves played so far.
+    pub fn score(&self) -> usize {
+        self.history.iter().map(|m| m.score).sum()
+    }
+
+    /// The bonuses earned for the tower the game ended with, or none while there are moves left.
+    pub fn end_bonuses(&self) -> Vec<crate::EndBonus> {
+        if self.is_over() {
+            crate::end_bonuses(&self.tower)
+        } else {
+            vec![]
+        }
+    }
+
+    /// The points scored so far, plus the end bonuses once the game is over.
+    pub fn total_score(&self) -> usize {
+        self.score()
+            + self
+                .end_bonuses()
+                .into_iter()
+                .map(crate::EndBonus::points)
+                .sum::<usize>()
+    }
+
+    /// Every move that can be played next.
+    pub fn legal_moves(&self) -> &[Path] {
+        self.legal_moves
+            .get_or_init(|| crate::find_paths(&self.tower, self.root))
+    }
+
+    /// Whether there are no moves left to play.
+    pub fn is_over(&self) -> bool {
+        self.legal_moves().is_empty()
+    }
+
+    /// Plays `path`, discarding anything that could have been redone.
+    pub fn play(&mut self, path: &[(usize, usize, char)]) -> Result<&Move, Error> {
+        validate_path(&self.tower, self.root, path)?;
+        self.undone.clear();
+        Ok(self.apply(path.to_vec()))
+    }
+
+    fn apply(&mut self, path: Path) -> &Move {
+        let score = crate::score_path(&self.tower, &path, &self.rules);
+
+        self.before.push(self.tower.clone());
+        let diff = crate::delete_path(&mut self.tower, &path);
+        self.legal_moves.take();
+
+        self.history.push(Move { path, score, diff });
+        self.history.last().unwrap()
+    }
+
+    /// Whether there is a move to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    /// Whether there is an undone move to play again.
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Takes back the last move, returning it.
+    pub fn undo(&mut self) -> Option<&Move> {
+        let m = self.history.pop()?;
+        self.tower = self.before.pop().unwrap();
+        self.legal_moves.take();
+        self.undone.push(m);
+        self.undone.last()
+    }
+
+    /// Plays the last undone move again, returning it.
+    pub fn redo(&mut self) -> Option<&Move> {
+        let m = self.undone.pop()?;
+        Some(self.apply(m.path))
+    }
+}
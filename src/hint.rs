@@ -0,0 +1,64 @@
+//! Suggesting the next move on a tower.
+
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+
+use crate::{Path, Rules, Tower};
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Hint {
+    pub path: Path,
+    /// The points for this move alone.
+    pub score: usize,
+    /// The points for this move, plus what a greedy player would score afterwards, including the
+    /// end bonus.
+    pub lookahead: usize,
+}
+
+/// Ranks the moves on `tower` by their immediate score, then estimates the best `candidates` of
+/// them by playing on greedily, and returns the best `n` by that estimate.
+pub fn hints(
+    tower: &Tower,
+    root: &crate::words::Node,
+    rules: &Rules,
+    n: usize,
+    candidates: usize,
+) -> Vec<Hint> {
+    hints_until(tower, root, rules, n, candidates, None)
+}
+
+/// Like [`hints`], but leaves out the candidates not yet looked ahead from by `deadline`.
+pub fn hints_until(
+    tower: &Tower,
+    root: &crate::words::Node,
+    rules: &Rules,
+    n: usize,
+    candidates: usize,
+    deadline: Option<std::time::Instant>,
+) -> Vec<Hint> {
+    let mut paths = crate::find_paths(tower, root)
+        .into_iter()
+        .map(|path| (crate::score_path(tower, &path, rules), path))
+        .collect::<Vec<_>>();
+    paths.sort_by(|(a, _), (b, _)| b.cmp(a));
+    paths.truncate(candidates.max(n));
+
+    let mut hints = paths
+        .into_par_iter()
+        .filter_map(|(score, path)| {
+            if deadline.is_some_and(|deadline| std::time::Instant::now() >= deadline) {
+                return None;
+            }
+            let mut tower = tower.clone();
+            crate::delete_path(&mut tower, &path);
+            let rest = crate::solve_greedy(&tower, root, rules);
+            Some(Hint {
+                lookahead: score + crate::score_solution(&tower, &rest, rules),
+                score,
+                path,
+            })
+        })
+        .collect::<Vec<_>>();
+    hints.sort_by(|a, b| b.lookahead.cmp(&a.lookahead).then(b.score.cmp(&a.score)));
+    hints.truncate(n);
+    hints
+}
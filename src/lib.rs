@@ -0,0 +1,764 @@
+pub mod analysis;
+pub mod annealers;
+pub mod compare;
+pub mod explain;
+pub mod game;
+pub mod hint;
+pub mod lookup;
+pub mod notation;
+pub mod planner;
+pub mod puzzle;
+pub mod puzzmo;
+pub mod reconstruct;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod report;
+pub mod share;
+pub mod style;
+pub mod tile;
+pub mod word_finder;
+pub mod words;
+
+use rand::seq::IteratorRandom as _;
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+
+pub use style::Style;
+pub use tile::Tile;
+
+/// A tower, indexed by `(row, column)` with row 0 at the top.
+pub type Tower = ndarray::Array2<Tile>;
+
+/// A word on the tower as a sequence of `(row, column, letter)` cells, where `letter` is the
+/// letter the tile was played as.
+pub type Path = Vec<(usize, usize, char)>;
+
+/// The words played on a tower, in order.
+pub type Solution = Vec<Path>;
+
+/// Everything that can go wrong in the library.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("puzzle: {0}")]
+    Puzzle(#[from] puzzle::Error),
+
+    #[error(transparent)]
+    Puzzmo(#[from] puzzmo::Error),
+
+    #[error("http: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("illegal move: {0}")]
+    IllegalMove(#[from] game::Error),
+
+    #[error("could not reconstruct game: {0}")]
+    Reconstruct(#[from] reconstruct::Error),
+
+    #[cfg(feature = "render")]
+    #[error("render: {0}")]
+    Render(#[from] render::Error),
+
+    #[error("could not find puzzle")]
+    PuzzleNotFound,
+}
+
+/// What a wildcard tile is worth when it is cleared.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WildcardScoring {
+    /// Wildcards are worth nothing, like blanks in Scrabble.
+    #[default]
+    Zero,
+    /// Wildcards are worth as much as the letter they stand for.
+    Full,
+}
+
+/// The scoring rules a game is played under, where they differ between versions of the game.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Rules {
+    pub wildcard_scoring: WildcardScoring,
+}
+
+const EIGHT_NEIGHBORS: &[(isize, isize)] = &[
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Every path on `tower` spelling a word in the dictionary rooted at `root`, with wildcards played
+/// as every letter that makes a word.
+pub fn find_paths(tower: &Tower, root: &words::Node) -> Vec<Path> {
+    fn candidates(tile: Tile, node: &words::Node) -> Vec<(char, &words::Node)> {
+        match tile {
+            Tile::Wildcard => node.children().collect(),
+            Tile::Letter { letter, .. } => node
+                .get(letter)
+                .map(|child| (letter, child))
+                .into_iter()
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn helper(
+        tower: &Tower,
+        path: &[(usize, usize, char)],
+        node: &words::Node,
+    ) -> Vec<Vec<(usize, usize, char)>> {
+        let mut paths = vec![];
+        if node.is_end() {
+            paths.push(path.to_vec());
+        }
+
+        let &(oi, oj, _) = path.last().unwrap();
+
+        for &(di, dj) in EIGHT_NEIGHBORS {
+            let Some(i) = oi.checked_add_signed(di) else {
+                continue;
+            };
+            let Some(j) = oj.checked_add_signed(dj) else {
+                continue;
+            };
+            if path.iter().any(|&(pi, pj, _)| (pi, pj) == (i, j)) {
+                continue;
+            }
+            let Some(&tile) = tower.get([i, j]) else {
+                continue;
+            };
+
+            for (letter, child) in candidates(tile, node) {
+                paths.extend(helper(
+                    tower,
+                    &path
+                        .iter()
+                        .cloned()
+                        .chain(std::iter::once((i, j, letter)))
+                        .collect::<Vec<_>>(),
+                    child,
+                ));
+            }
+        }
+
+        paths
+    }
+
+    let (n, m) = tower.dim();
+
+    (0..n)
+        .into_par_iter()
+        .flat_map(|i| (0..m).into_par_iter().map(move |j| (i, j)))
+        .flat_map(|(i, j)| {
+            candidates(tower[[i, j]], root)
+                .into_iter()
+                .flat_map(|(letter, child)| helper(tower, &[(i, j, letter)], child))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Finds every path on `tower` spelling `word`, which must be upper-case, whether or not it is in
+/// the dictionary.
+pub fn find_word_paths(tower: &Tower, word: &str) -> Vec<Path> {
+    fn helper(tower: &Tower, path: &mut Path, rest: &[char], paths: &mut Vec<Path>) {
+        let Some((&letter, rest)) = rest.split_first() else {
+            paths.push(path.clone());
+            return;
+        };
+
+        let &(oi, oj, _) = path.last().unwrap();
+        for &(di, dj) in EIGHT_NEIGHBORS {
+            let (Some(i), Some(j)) = (oi.checked_add_signed(di), oj.checked_add_signed(dj)) else {
+                continue;
+            };
+            if path.iter().any(|&(pi, pj, _)| (pi, pj) == (i, j)) {
+                continue;
+            }
+            if !tower.get([i, j]).is_some_and(|&tile| tile.can_be(letter)) {
+                continue;
+            }
+            path.push((i, j, letter));
+            helper(tower, path, rest, paths);
+            path.pop();
+        }
+    }
+
+    let letters = word.chars().collect::<Vec<_>>();
+    let Some((&first, rest)) = letters.split_first() else {
+        return vec![];
+    };
+
+    let mut paths = vec![];
+    for ((i, j), &tile) in tower.indexed_iter() {
+        if tile.can_be(first) {
+            helper(tower, &mut vec![(i, j, first)], rest, &mut paths);
+        }
+    }
+    paths
+}
+
+/// Whether `tower` has enough tiles, in any arrangement, to spell the upper-case `word`. Nothing
+/// spells a word with anything other than the letters A to Z in it.
+pub fn has_letters(tower: &Tower, word: &str) -> bool {
+    let mut counts = [0usize; 26];
+    let mut wildcards = 0;
+    for tile in tower.iter() {
+        match *tile {
+            Tile::Letter { letter, .. } if letter.is_ascii_uppercase() => {
+                counts[letter as usize - 'A' as usize] += 1
+            }
+            Tile::Wildcard => wildcards += 1,
+            _ => {}
+        }
+    }
+
+    let mut missing = 0;
+    for c in word.chars() {
+        if !c.is_ascii_uppercase() {
+            return false;
+        }
+        let count = &mut counts[c as usize - 'A' as usize];
+        match count.checked_sub(1) {
+            Some(n) => *count = n,
+            None => missing += 1,
+        }
+    }
+    missing <= wildcards
+}
+
+/// The points for the upper-case letter `c`, or 0 for anything else.
+pub fn score_letter(c: char) -> usize {
+    match c {
+        'A' => 1,
+        'B' => 4,
+        'C' => 4,
+        'D' => 3,
+        'E' => 1,
+        'F' => 5,
+        'G' => 3,
+        'H' => 5,
+        'I' => 1,
+        'J' => 9,
+        'K' => 6,
+        'L' => 2,
+        'M' => 4,
+        'N' => 2,
+        'O' => 1,
+        'P' => 4,
+        'Q' => 12,
+        'R' => 2,
+        'S' => 1,
+        'T' => 2,
+        'U' => 1,
+        'V' => 5,
+        'W' => 5,
+        'X' => 9,
+        'Y' => 5,
+        'Z' => 11,
+        _ => 0,
+    }
+}
+
+/// Why a tile was cleared by a move.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ClearReason {
+    /// The tile is part of the word.
+    Path,
+    /// The tile is in the same row as a J, Q, X or Z in the word.
+    RareLetterRow,
+    /// The tile is a blank next to the word.
+    Blank,
+    /// The tile is next to a word of 5 or more letters.
+    Adjacent,
+}
+
+/// A tile cleared by a move.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cleared {
+    pub cell: (usize, usize),
+    pub tile: Tile,
+    pub reason: ClearReason,
+}
+
+/// A tile that fell down its column after a move.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Fall {
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+    pub tile: Tile,
+}
+
+/// What a move did to the tower: the tiles it cleared, then the tiles that fell into the gaps.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Diff {
+    pub cleared: Vec<Cleared>,
+    pub fallen: Vec<Fall>,
+}
+
+/// Every cell cleared by `path`, with the first reason it was cleared for.
+pub fn clear_reasons(
+    tower: &Tower,
+    path: &[(usize, usize, char)],
+) -> std::collections::HashMap<(usize, usize), ClearReason> {
+    let (_, m) = tower.dim();
+
+    let mut collected = path
+        .iter()
+        .map(|&(i, j, _)| ((i, j), ClearReason::Path))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    for &(i, _, letter) in path.iter() {
+        if !matches!(letter, 'J' | 'Q' | 'X' | 'Z') {
+            continue;
+        }
+        for j in 0..m {
+            if !tower[[i, j]].is_empty() {
+                collected
+                    .entry((i, j))
+                    .or_insert(ClearReason::RareLetterRow);
+            }
+        }
+    }
+
+    for (oi, oj, _) in path.iter() {
+        for (di, dj) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let Some(i) = oi.checked_add_signed(di) else {
+                continue;
+            };
+            let Some(j) = oj.checked_add_signed(dj) else {
+                continue;
+            };
+            let Some(&tile) = tower.get([i, j]) else {
+                continue;
+            };
+            if tile.is_empty() {
+                continue;
+            }
+            if tile == Tile::Blank {
+                collected.entry((i, j)).or_insert(ClearReason::Blank);
+            } else if path.len() >= 5 {
+                collected.entry((i, j)).or_insert(ClearReason::Adjacent);
+            }
+        }
+    }
+
+    collected
+}
+
+/// Every cell cleared by `path`.
+pub fn deletable(
+    tower: &Tower,
+    path: &[(usize, usize, char)],
+) -> std::collections::HashSet<(usize, usize)> {
+    clear_reasons(tower, path).into_keys().collect()
+}
+
+/// Plays `path` on `tower`: clears everything it clears and lets the tiles above fall into the
+/// gaps.
+pub fn delete_path(tower: &mut Tower, path: &[(usize, usize, char)]) -> Diff {
+    let (n, m) = tower.dim();
+
+    let mut diff = Diff::default();
+
+    for ((i, j), reason) in clear_reasons(tower, path) {
+        diff.cleared.push(Cleared {
+            cell: (i, j),
+            tile: tower[[i, j]],
+            reason,
+        });
+        tower[[i, j]] = Tile::Empty;
+    }
+    diff.cleared.sort_unstable_by_key(|c| c.cell);
+
+    for j in 0..m {
+        let mut i2 = n - 1;
+
+        'top: for i in (0..n).rev() {
+            let Some(next_i2) = i2.checked_sub(1) else {
+                break 'top;
+            };
+            i2 = next_i2;
+
+            if tower[(i, j)].is_empty() {
+                while tower[(i2, j)].is_empty() {
+                    let Some(next_i2) = i2.checked_sub(1) else {
+                        break 'top;
+                    };
+                    i2 = next_i2;
+                }
+                tower[(i, j)] = tower[(i2, j)];
+                tower[(i2, j)] = Tile::Empty;
+                diff.fallen.push(Fall {
+                    from: (i2, j),
+                    to: (i, j),
+                    tile: tower[(i, j)],
+                });
+            }
+        }
+    }
+
+    diff
+}
+
+/// The points a single cleared cell contributes to the letter sum of `path`.
+pub fn score_cell(
+    tower: &Tower,
+    path: &[(usize, usize, char)],
+    rules: &Rules,
+    (i, j): (usize, usize),
+) -> usize {
+    match tower[[i, j]] {
+        Tile::Letter { letter, .. } => score_letter(letter),
+        Tile::Wildcard => match rules.wildcard_scoring {
+            WildcardScoring::Zero => 0,
+            WildcardScoring::Full => path
+                .iter()
+                .find(|&&(pi, pj, _)| (pi, pj) == (i, j))
+                .map_or(0, |&(_, _, letter)| score_letter(letter)),
+        },
+        _ => 0,
+    }
+}
+
+/// What the score of `path` is multiplied by: 1, plus 1 for every bonus tile in it.
+pub fn bonus_multiplier(tower: &Tower, path: &[(usize, usize, char)]) -> usize {
+    path.iter()
+        .filter(|&&(i, j, _)| tower[[i, j]].is_bonus())
+        .count()
+        + 1
+}
+
+/// The points for playing `path` on `tower`: the letters of every tile it clears, times the length
+/// of the word, times the [`bonus_multiplier`].
+pub fn score_path(tower: &Tower, path: &[(usize, usize, char)], rules: &Rules) -> usize {
+    deletable(tower, path)
+        .into_iter()
+        .map(|cell| score_cell(tower, path, rules, cell))
+        .sum::<usize>()
+        * path.len()
+        * bonus_multiplier(tower, path)
+}
+
+/// Whether no column of `tower` has more than 2 tiles left.
+pub fn is_almost_there(tower: &Tower) -> bool {
+    tower
+        .columns()
+        .into_iter()
+        .all(|row| row.into_iter().filter(|x| !x.is_empty()).count() <= 2)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndBonus {
+    /// No column has more than 2 tiles left.
+    AlmostThere,
+    /// The tower is completely empty.
+    Cleared,
+}
+
+impl EndBonus {
+    /// What the bonus is worth.
+    pub fn points(self) -> usize {
+        match self {
+            EndBonus::AlmostThere => 1000,
+            EndBonus::Cleared => 1000,
+        }
+    }
+}
+
+impl std::fmt::Display for EndBonus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EndBonus::AlmostThere => f.write_str("almost there"),
+            EndBonus::Cleared => f.write_str("tower cleared"),
+        }
+    }
+}
+
+/// The bonuses earned for the state the tower is left in at the end of the game.
+pub fn end_bonuses(tower: &Tower) -> Vec<EndBonus> {
+    let mut bonuses = vec![];
+
+    if is_almost_there(tower) {
+        bonuses.push(EndBonus::AlmostThere);
+    }
+
+    if tower.iter().all(|x| x.is_empty()) {
+        bonuses.push(EndBonus::Cleared);
+    }
+
+    bonuses
+}
+
+/// The points for every bonus in [`end_bonuses`].
+pub fn end_bonus(tower: &Tower) -> usize {
+    end_bonuses(tower).into_iter().map(EndBonus::points).sum()
+}
+
+/// The points for playing every move of `solution` in order, plus the end bonus.
+pub fn score_solution(
+    tower: &Tower,
+    solution: &[impl AsRef<[(usize, usize, char)]>],
+    rules: &Rules,
+) -> usize {
+    let mut tower = tower.clone();
+
+    let mut total_score = 0;
+    for path in solution {
+        let score = score_path(&tower, path.as_ref(), rules);
+        total_score += score;
+        delete_path(&mut tower, path.as_ref());
+    }
+
+    total_score + end_bonus(&tower)
+}
+
+/// The running total after each move of `solution`, not counting the end bonus.
+pub fn cumulative_scores(
+    tower: &Tower,
+    solution: &[impl AsRef<[(usize, usize, char)]>],
+    rules: &Rules,
+) -> Vec<usize> {
+    let mut tower = tower.clone();
+
+    let mut total_score = 0;
+    solution
+        .iter()
+        .map(|path| {
+            total_score += score_path(&tower, path.as_ref(), rules);
+            delete_path(&mut tower, path.as_ref());
+            total_score
+        })
+        .collect()
+}
+
+/// Changes `solution` a little, for annealing: drops the moves from a random point on, plays a
+/// random move instead, and finishes greedily.
+///
+/// Does nothing to an empty solution, or if there is no move to play instead.
+pub fn nudge_solution(
+    tower: &Tower,
+    root: &words::Node,
+    rules: &Rules,
+    solution: &mut Solution,
+    rng: &mut impl rand::Rng,
+) {
+    if solution.is_empty() {
+        return;
+    }
+    let k = rng.gen_range(0..solution.len());
+
+    let mut tower = tower.clone();
+    for path in solution[..k].iter() {
+        delete_path(&mut tower, path);
+    }
+
+    let Some(path) = find_paths(&tower, root).into_iter().choose(rng) else {
+        return;
+    };
+    solution.truncate(k);
+    delete_path(&mut tower, &path);
+    solution.push(path);
+
+    while let Some(best) = find_paths(&tower, root)
+        .into_iter()
+        .max_by_key(|path| score_path(&tower, path, rules))
+    {
+        delete_path(&mut tower, &best);
+        solution.push(best);
+    }
+}
+
+/// Plays the highest-scoring move until there are none left.
+pub fn solve_greedy(tower: &Tower, root: &words::Node, rules: &Rules) -> Solution {
+    let mut tower = tower.clone();
+    let mut solution = vec![];
+
+    while let Some(best) = find_paths(&tower, root)
+        .into_iter()
+        .max_by_key(|path| score_path(&tower, path, rules))
+    {
+        delete_path(&mut tower, &best);
+        solution.push(best);
+    }
+
+    solution
+}
+
+/// Draws `tower` for the terminal, with the tiles of `path` and the tiles it would clear
+/// highlighted.
+pub fn pretty_tower(
+    tower: &Tower,
+    path: &[(usize, usize, char)],
+    rules: &Rules,
+    style: &Style,
+) -> String {
+    let (n, m) = tower.dim();
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum LinkType {
+        None,
+        Vertical,
+        Horizontal,
+        Diagonal,
+        Antidiagonal,
+        Cross,
+    }
+
+    let mut links = ndarray::Array2::from_elem((n * 2 + 1, m * 2 + 1), LinkType::None);
+    let deletable: std::collections::HashSet<(usize, usize)> = deletable(tower, path);
+
+    if !path.is_empty() {
+        for (&(ia, ja, _), &(ib, jb, _)) in path.iter().zip(path[1..].iter()) {
+            let li = ia * 2 + 1;
+            let lj = ja * 2 + 1;
+
+            let di = ib as isize - ia as isize;
+            let dj = jb as isize - ja as isize;
+
+            let l = &mut links[[(li as isize + di) as usize, (lj as isize + dj) as usize]];
+            match (di, dj) {
+                (-1, 0) | (1, 0) => {
+                    *l = LinkType::Vertical;
+                }
+                (0, -1) | (0, 1) => {
+                    *l = LinkType::Horizontal;
+                }
+                (-1, -1) | (1, 1) => {
+                    *l = if *l != LinkType::Antidiagonal {
+                        LinkType::Diagonal
+                    } else {
+                        LinkType::Cross
+                    };
+                }
+                (1, -1) | (-1, 1) => {
+                    *l = if *l != LinkType::Diagonal {
+                        LinkType::Antidiagonal
+                    } else {
+                        LinkType::Cross
+                    };
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    let mut pretty = ndarray::Array2::from_elem((n * 2 + 1, m * 2 + 1), " ".to_string());
+
+    for (i, j) in (0..n).flat_map(|i| (0..m).map(move |j| (i, j))) {
+        let pi = i * 2 + 1;
+        let pj = j * 2 + 1;
+
+        for &(di, dj) in EIGHT_NEIGHBORS {
+            let li = (pi as isize + di) as usize;
+            let lj = (pj as isize + dj) as usize;
+
+            let link = match links[[li, lj]] {
+                LinkType::None => {
+                    pretty[[li, lj]] = if dj == 0 { "   " } else { " " }.to_string();
+                    continue;
+                }
+                LinkType::Vertical => style.pick(" │ ", " | "),
+                LinkType::Horizontal => style.pick("─", "-"),
+                LinkType::Diagonal => style.pick("╲", "\\"),
+                LinkType::Antidiagonal => style.pick("╱", "/"),
+                LinkType::Cross => style.pick("╳", "X"),
+            };
+            pretty[[li, lj]] = style.paint(link, |t| t.link);
+        }
+
+        let c = match tower[[i, j]] {
+            Tile::Blank => style.pick("░", "#").to_string(),
+            tile => char::from(tile).to_string(),
+        };
+        let in_path = path.iter().any(|&(pi, pj, _)| (pi, pj) == (i, j));
+        pretty[[pi, pj]] = match style.theme {
+            Some(_) if path.first().map(|&(i, j, _)| (i, j)) == Some((i, j)) => {
+                style.paint(&format!(" {c} "), |t| t.first)
+            }
+            Some(_) if in_path => style.paint(&format!(" {c} "), |t| t.path),
+            Some(_) if deletable.contains(&(i, j)) => {
+                style.paint(&format!(" {c} "), |t| t.deletable)
+            }
+            None if in_path => format!("[{c}]"),
+            None if deletable.contains(&(i, j)) => format!("*{c}*"),
+            _ => format!(" {c} "),
+        };
+    }
+
+    let word = path
+        .iter()
+        .map(|&(i, j, letter)| {
+            if tower[[i, j]].is_bonus() {
+                letter.to_ascii_lowercase()
+            } else {
+                letter
+            }
+        })
+        .collect::<String>();
+    let score = score_path(tower, path, rules);
+
+    let border_length = (m * 4).saturating_sub(1);
+    let horizontal = style.pick("═", "-");
+    let vertical = style.pick("║", "|");
+
+    let bottom_border = horizontal.repeat(border_length);
+
+    let top_border = if !path.is_empty() {
+        let header = format!("{word:} ({score:})");
+        format!(
+            "{horizontal}{header}{}",
+            horizontal.repeat(
+                border_length
+                    .saturating_sub(1)
+                    .saturating_sub(header.chars().count()),
+            )
+        )
+    } else {
+        bottom_border.clone()
+    };
+
+    // Rows are labelled the way [`notation`] numbers them, and only rows of tiles get a label, not
+    // the rows of links between them.
+    let label_width = if style.labels { n.to_string().len() } else { 0 };
+    let label = |k: Option<usize>| match k {
+        Some(k) if style.labels => format!("{:>label_width$}", k + 1),
+        _ => " ".repeat(label_width),
+    };
+
+    let body = pretty
+        .slice(ndarray::s![1..n * 2, 1..m * 2])
+        .rows()
+        .into_iter()
+        .enumerate()
+        .map(|(k, cols)| {
+            format!(
+                "{}{vertical}{}{vertical}",
+                label((k % 2 == 0).then_some(k / 2)),
+                cols.into_iter().flat_map(|v| v.chars()).collect::<String>()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (top_left, top_right, bottom_left, bottom_right) = match style.charset {
+        style::Charset::Unicode => ("╔", "╗", "╚", "╝"),
+        style::Charset::Ascii => ("+", "+", "+", "+"),
+    };
+    let frame = format!(
+        "{0}{top_left}{top_border}{top_right}\n{body}\n{0}{bottom_left}{bottom_border}{bottom_right}",
+        label(None)
+    );
+
+    if !style.labels {
+        return frame;
+    }
+
+    let columns = (0..m)
+        .map(|j| format!("{:^3}", notation::column_name(j)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("{} {columns} \n{frame}", label(None))
+}
@@ -0,0 +1,176 @@
+//! Questions about the dictionary itself, independent of any tower.
+
+use crate::{words, Tile, Tower};
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("{0:?} is not a letter, ? or *")]
+    InvalidPattern(char),
+
+    #[error("{0:?} is not a letter or ?")]
+    InvalidLetter(char),
+}
+
+/// Calls `f` with every word under `node`, in alphabetical order, where `word` spells the way
+/// to `node`.
+fn each_word(node: &words::Node, word: &mut String, f: &mut impl FnMut(&str)) {
+    if node.is_end() {
+        f(word);
+    }
+    for (c, child) in node.children() {
+        word.push(c);
+        each_word(child, word, f);
+        word.pop();
+    }
+}
+
+/// Every word starting with `prefix`, in alphabetical order.
+pub fn with_prefix(root: &words::Node, prefix: &str) -> Vec<String> {
+    let prefix = prefix.to_ascii_uppercase();
+    let mut found = vec![];
+    if let Some(node) = prefix.chars().try_fold(root, |node, c| node.get(c)) {
+        each_word(node, &mut prefix.clone(), &mut |word| {
+            found.push(word.to_string())
+        });
+    }
+    found
+}
+
+/// Every word matching `pattern`, in alphabetical order, where `?` stands for any one letter and
+/// `*` for any number of letters, including none.
+pub fn matching(root: &words::Node, pattern: &str) -> Result<Vec<String>, Error> {
+    fn go(node: &words::Node, pattern: &[u8], word: &mut String, found: &mut Vec<String>) {
+        let Some((&first, rest)) = pattern.split_first() else {
+            if node.is_end() {
+                found.push(word.clone());
+            }
+            return;
+        };
+        match first {
+            b'*' => {
+                go(node, rest, word, found);
+                for (c, child) in node.children() {
+                    word.push(c);
+                    go(child, pattern, word, found);
+                    word.pop();
+                }
+            }
+            b'?' => {
+                for (c, child) in node.children() {
+                    word.push(c);
+                    go(child, rest, word, found);
+                    word.pop();
+                }
+            }
+            letter => {
+                if let Some(child) = node.get(letter as char) {
+                    word.push(letter as char);
+                    go(child, rest, word, found);
+                    word.pop();
+                }
+            }
+        }
+    }
+
+    let pattern = pattern.to_ascii_uppercase();
+    if let Some(c) = pattern
+        .chars()
+        .find(|&c| !c.is_ascii_uppercase() && c != '?' && c != '*')
+    {
+        return Err(Error::InvalidPattern(c));
+    }
+    let mut pattern = pattern.into_bytes();
+    pattern.dedup_by(|a, b| *a == b'*' && *b == b'*');
+
+    let mut found = vec![];
+    go(root, &pattern, &mut String::new(), &mut found);
+    // A star can match the same word in more than one way.
+    found.sort_unstable();
+    found.dedup();
+    Ok(found)
+}
+
+/// A multiset of letters to make words from.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Letters {
+    counts: [usize; 26],
+    /// How many of the letters can stand for any letter.
+    wildcards: usize,
+}
+
+impl Letters {
+    /// Reads letters in any case, with `?` for a wildcard.
+    pub fn parse(s: &str) -> Result<Letters, Error> {
+        let mut letters = Letters::default();
+        for c in s.chars() {
+            match c.to_ascii_uppercase() {
+                '?' => letters.wildcards += 1,
+                c @ 'A'..='Z' => letters.counts[c as usize - 'A' as usize] += 1,
+                c => return Err(Error::InvalidLetter(c)),
+            }
+        }
+        Ok(letters)
+    }
+
+    /// The letters and wildcards on `tower`.
+    pub fn from_tower(tower: &Tower) -> Letters {
+        let mut letters = Letters::default();
+        for &tile in tower.iter() {
+            match tile {
+                Tile::Letter { letter, .. } => letters.counts[letter as usize - 'A' as usize] += 1,
+                Tile::Wildcard => letters.wildcards += 1,
+                _ => {}
+            }
+        }
+        letters
+    }
+
+    /// How many letters and wildcards there are.
+    pub fn len(&self) -> usize {
+        self.counts.iter().sum::<usize>() + self.wildcards
+    }
+
+    /// Whether there are no letters or wildcards.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Every word that can be made from some of `letters`, longest first and then in alphabetical
+/// order.
+pub fn anagrams(root: &words::Node, letters: &Letters) -> Vec<String> {
+    fn go(node: &words::Node, letters: &mut Letters, word: &mut String, found: &mut Vec<String>) {
+        if node.is_end() {
+            found.push(word.clone());
+        }
+        for (c, child) in node.children() {
+            let k = c as usize - 'A' as usize;
+            // Use the letter itself where there is one, saving wildcards for letters there aren't.
+            let wildcard = letters.counts[k] == 0;
+            if wildcard && letters.wildcards == 0 {
+                continue;
+            }
+            let count = if wildcard {
+                &mut letters.wildcards
+            } else {
+                &mut letters.counts[k]
+            };
+            *count -= 1;
+
+            word.push(c);
+            go(child, letters, word, found);
+            word.pop();
+
+            if wildcard {
+                letters.wildcards += 1;
+            } else {
+                letters.counts[k] += 1;
+            }
+        }
+    }
+
+    let mut found = vec![];
+    go(root, &mut letters.clone(), &mut String::new(), &mut found);
+    found.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    found
+}
@@ -1,497 +1,1078 @@
-mod annealers;
+mod engine;
 mod observer;
-mod puzzmo;
-mod words;
-
-use clap::{Parser as _, ValueEnum as _};
-use rand::{seq::IteratorRandom, SeedableRng as _};
-use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
-
-type Tower = ndarray::Array2<char>;
-
-const EIGHT_NEIGHBORS: &[(isize, isize)] = &[
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
-
-fn find_paths(tower: &Tower, root: &words::Node) -> Vec<Vec<(usize, usize)>> {
-    fn helper(
-        tower: &Tower,
-        path: &[(usize, usize)],
-        node: &words::Node,
-    ) -> Vec<Vec<(usize, usize)>> {
-        let mut paths = vec![];
-        if node.is_end() {
-            paths.push(path.to_vec());
-        }
-
-        let &(oi, oj) = path.last().unwrap();
-
-        for &(di, dj) in EIGHT_NEIGHBORS {
-            let Some(i) = oi.checked_add_signed(di) else {
-                continue;
-            };
-            let Some(j) = oj.checked_add_signed(dj) else {
-                continue;
-            };
-            if path.contains(&(i, j)) {
-                continue;
-            }
-            let Some(&letter) = tower.get([i, j]) else {
-                continue;
-            };
-            let Some(child) = node.get(letter.to_ascii_uppercase()) else {
-                continue;
-            };
+#[cfg(feature = "tui")]
+mod playback;
+#[cfg(feature = "server")]
+mod server;
+#[cfg(feature = "tui")]
+mod tui;
+
+use clap::{CommandFactory as _, FromArgMatches as _, ValueEnum as _};
+use rand::SeedableRng as _;
+use spelltower::{
+    analysis, annealers, compare, delete_path, explain, game, hint, lookup, notation, planner,
+    pretty_tower, puzzle, puzzmo, reconstruct, score_solution, word_finder, words,
+};
+
+#[derive(clap::ValueEnum, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum WildcardScoring {
+    /// Wildcards are worth nothing.
+    Zero,
+    /// Wildcards are worth as much as the letter they stand for.
+    Full,
+}
 
-            paths.extend(helper(
-                tower,
-                &path
-                    .iter()
-                    .cloned()
-                    .chain(std::iter::once((i, j)))
-                    .collect::<Vec<_>>(),
-                child,
-            ));
+impl WildcardScoring {
+    pub fn as_wildcard_scoring(&self) -> spelltower::WildcardScoring {
+        match self {
+            WildcardScoring::Zero => spelltower::WildcardScoring::Zero,
+            WildcardScoring::Full => spelltower::WildcardScoring::Full,
         }
-
-        paths
     }
+}
 
-    let (n, m) = tower.dim();
+#[derive(clap::ValueEnum, Clone, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Coster {
+    TotalScore,
+    LongestWord,
+    BestWord,
+    FewestWords,
+}
 
-    (0..n)
-        .into_par_iter()
-        .flat_map(|i| (0..m).into_par_iter().map(move |j| (i, j)))
-        .flat_map(|(i, j)| {
-            let Some(child) = root.get(tower[[i, j]].to_ascii_uppercase()) else {
-                return vec![];
-            };
-            helper(tower, &[(i, j)], child)
-        })
-        .collect::<Vec<_>>()
-}
-
-fn score_letter(c: char) -> usize {
-    match c {
-        'A' => 1,
-        'B' => 4,
-        'C' => 4,
-        'D' => 3,
-        'E' => 1,
-        'F' => 5,
-        'G' => 3,
-        'H' => 5,
-        'I' => 1,
-        'J' => 9,
-        'K' => 6,
-        'L' => 2,
-        'M' => 4,
-        'N' => 2,
-        'O' => 1,
-        'P' => 4,
-        'Q' => 12,
-        'R' => 2,
-        'S' => 1,
-        'T' => 2,
-        'U' => 1,
-        'V' => 5,
-        'W' => 5,
-        'X' => 9,
-        'Y' => 5,
-        'Z' => 11,
-        _ => 0,
+impl Coster {
+    pub fn as_coster(&self) -> &'static annealers::Coster {
+        match self {
+            Coster::LongestWord => &annealers::LONGEST_WORD,
+            Coster::TotalScore => &annealers::TOTAL_SCORE,
+            Coster::BestWord => &annealers::BEST_WORD,
+            Coster::FewestWords => &annealers::FEWEST_WORDS,
+        }
     }
 }
 
-fn deletable(tower: &Tower, path: &[(usize, usize)]) -> std::collections::HashSet<(usize, usize)> {
-    let (_, m) = tower.dim();
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum Color {
+    /// Use colour when writing to a terminal, unless `NO_COLOR` is set.
+    Auto,
+    Always,
+    Never,
+}
 
-    let mut collected = path
-        .iter()
-        .cloned()
-        .collect::<std::collections::HashSet<_>>();
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum Theme {
+    Magenta,
+    Blue,
+    Green,
+    Monochrome,
+}
 
-    for &(i, j) in path.iter() {
-        let c = tower[[i, j]].to_ascii_uppercase();
-        if !matches!(c, 'J' | 'Q' | 'X' | 'Z') {
-            continue;
+impl Theme {
+    pub fn as_theme(&self) -> spelltower::style::Theme {
+        match self {
+            Theme::Magenta => spelltower::style::Theme::MAGENTA,
+            Theme::Blue => spelltower::style::Theme::BLUE,
+            Theme::Green => spelltower::style::Theme::GREEN,
+            Theme::Monochrome => spelltower::style::Theme::MONOCHROME,
         }
-        collected.extend(
-            (0..m)
-                .map(|j| (i, j))
-                .filter(|&(i, j)| tower[[i, j]] != '\0'),
-        );
     }
+}
 
-    for (oi, oj) in path.iter() {
-        for (di, dj) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-            let Some(i) = oi.checked_add_signed(di) else {
-                continue;
-            };
-            let Some(j) = oj.checked_add_signed(dj) else {
-                continue;
-            };
-            let Some(&letter) = tower.get([i, j]) else {
-                continue;
-            };
-            if letter == '\0' {
-                continue;
-            }
-            if path.len() >= 5 || letter == '_' {
-                collected.insert((i, j));
+#[derive(clap::Args)]
+struct StyleArgs {
+    /// Draw towers with plain ASCII instead of box-drawing characters.
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    #[arg(long, global = true, default_value = "auto")]
+    color: Color,
+
+    #[arg(long, global = true, default_value = "magenta")]
+    theme: Theme,
+
+    /// Label the rows and columns of towers.
+    #[arg(long, global = true)]
+    labels: bool,
+}
+
+impl StyleArgs {
+    fn style(&self) -> spelltower::Style {
+        use std::io::IsTerminal as _;
+
+        let color = match self.color {
+            Color::Always => true,
+            Color::Never => false,
+            Color::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && std::io::stdout().is_terminal()
             }
+        };
+
+        spelltower::Style {
+            charset: if self.ascii {
+                spelltower::style::Charset::Ascii
+            } else {
+                spelltower::style::Charset::Unicode
+            },
+            theme: color.then(|| self.theme.as_theme()),
+            labels: self.labels,
         }
     }
-
-    collected
 }
 
-fn delete_path(tower: &mut Tower, path: &[(usize, usize)]) {
-    let (n, m) = tower.dim();
+#[derive(clap::Args)]
+struct PuzzleArgs {
+    #[arg(long, global = true)]
+    day: Option<String>,
+
+    #[arg(long, global = true)]
+    status: Option<String>,
+
+    #[arg(long, global = true, default_value = "spelltower")]
+    game: String,
+
+    /// Read the puzzle from a file in Puzzmo text format instead of fetching it.
+    #[arg(long, global = true)]
+    puzzle: Option<std::path::PathBuf>,
 
-    for (i, j) in deletable(tower, path) {
-        tower[[i, j]] = '\0';
+    #[arg(long, global = true, default_value = "zero")]
+    wildcard_scoring: WildcardScoring,
+}
+
+impl PuzzleArgs {
+    fn rules(&self) -> spelltower::Rules {
+        spelltower::Rules {
+            wildcard_scoring: self.wildcard_scoring.as_wildcard_scoring(),
+        }
     }
 
-    for j in 0..m {
-        let mut i2 = n - 1;
+    fn load(&self) -> anyhow::Result<puzzmo::Puzzle> {
+        if let Some(path) = &self.puzzle {
+            return Ok(puzzmo::Puzzle {
+                day: self.day.clone().unwrap_or_default(),
+                is_today: false,
+                puzzle: std::fs::read_to_string(path)?,
+            });
+        }
 
-        'top: for i in (0..n).rev() {
-            let Some(next_i2) = i2.checked_sub(1) else {
-                break 'top;
-            };
-            i2 = next_i2;
-
-            if tower[(i, j)] == '\0' {
-                while tower[(i2, j)] == '\0' {
-                    let Some(next_i2) = i2.checked_sub(1) else {
-                        break 'top;
-                    };
-                    i2 = next_i2;
+        Ok(puzzmo::load(
+            &self.game,
+            self.status.as_deref().unwrap_or_else(|| {
+                if self.game == "cubeclear" {
+                    "Experimental"
+                } else {
+                    "Vanilla"
                 }
-                tower[(i, j)] = tower[(i2, j)];
-                tower[(i2, j)] = '\0';
-            }
-        }
+            }),
+            self.day.clone(),
+        )?)
     }
 }
 
-fn score_path(tower: &Tower, path: &[(usize, usize)]) -> usize {
-    deletable(tower, &path)
-        .iter()
-        .map(|&(i, j)| score_letter(tower[[i, j]].to_ascii_uppercase()))
-        .sum::<usize>()
-        * path.len()
-        * (path
-            .iter()
-            .filter(|&&(i, j)| tower[[i, j]].is_ascii_lowercase())
-            .count()
-            + 1)
+#[derive(clap::Args)]
+struct SolveArgs {
+    #[arg(long, default_value = "total-score")]
+    coster: Coster,
+
+    #[arg(long)]
+    allow_leftovers: bool,
+
+    #[arg(long, default_value_t = 1000.0)]
+    initial_temperature: f64,
+
+    #[arg(long, default_value_t = 5000)]
+    reannealing_fixed: u64,
+
+    /// Show where the points for each move came from.
+    #[arg(long)]
+    explain: bool,
+
+    /// Write the solution to this file as JSON.
+    #[arg(long)]
+    save_solution: Option<std::path::PathBuf>,
+
+    /// Write an HTML page replaying the solution to this file.
+    #[arg(long)]
+    report: Option<std::path::PathBuf>,
 }
 
-fn is_almost_there(tower: &Tower) -> bool {
-    tower
-        .columns()
-        .into_iter()
-        .all(|row| row.into_iter().filter(|&&x| x != '\0').count() <= 2)
+#[derive(clap::ValueEnum, Clone)]
+enum WordsSort {
+    Score,
+    Length,
+    Cleared,
+    Word,
 }
 
-fn score_solution(tower: &Tower, solution: &[impl AsRef<[(usize, usize)]>]) -> usize {
-    let mut tower = tower.clone();
+#[derive(clap::Args)]
+struct WordsArgs {
+    #[arg(long, default_value = "score")]
+    sort: WordsSort,
 
-    let mut total_score = 0;
-    for path in solution {
-        let score = score_path(&tower, path.as_ref());
-        total_score += score;
-        delete_path(&mut tower, path.as_ref());
-    }
+    #[arg(long)]
+    min_length: Option<usize>,
 
-    if is_almost_there(&tower) {
-        total_score += 1000;
-    }
+    #[arg(long)]
+    max_length: Option<usize>,
 
-    if tower.iter().filter(|&&x| x == '\0').count() == tower.len() {
-        total_score += 1000;
-    }
+    #[arg(long)]
+    min_score: Option<usize>,
 
-    total_score
+    /// Only show words containing all of these letters, as many times as they are given.
+    #[arg(long)]
+    containing: Option<String>,
+
+    /// Only show words using this tile, in move notation, e.g. `C4`.
+    #[arg(long, value_parser = notation::parse_cell)]
+    cell: Option<(usize, usize)>,
+
+    #[arg(long)]
+    limit: Option<usize>,
+
+    #[arg(long)]
+    json: bool,
 }
 
-fn nudge_solution(
-    tower: &Tower,
-    root: &words::Node,
-    solution: &mut Vec<Vec<(usize, usize)>>,
-    rng: &mut impl rand::Rng,
-) {
-    solution.drain(rng.gen_range(0..solution.len())..);
+#[derive(clap::Args)]
+struct HintArgs {
+    /// How many moves to suggest.
+    #[arg(short, default_value_t = 5)]
+    n: usize,
 
-    let mut tower = tower.clone();
-    for path in solution.iter() {
-        delete_path(&mut tower, path);
-    }
+    /// How many of the highest-scoring moves to look ahead from.
+    #[arg(long, default_value_t = 20)]
+    candidates: usize,
+}
+
+#[derive(clap::Args)]
+struct PlanArgs {
+    /// The word to set up. If not given, set up the longest word possible.
+    word: Option<String>,
+
+    /// The most preparatory moves to try.
+    #[arg(long, default_value_t = 3)]
+    depth: usize,
 
-    let path = find_paths(&tower, root).into_iter().choose(rng).unwrap();
-    delete_path(&mut tower, &path);
-    solution.push(path);
+    /// How many positions to keep at each depth.
+    #[arg(long, default_value_t = 100, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    beam_width: usize,
 
-    loop {
-        let Some(best) = find_paths(&tower, root)
-            .into_iter()
-            .max_by_key(|path| score_path(&tower, &path))
-        else {
-            break;
+    /// Prefer the highest-scoring setup over the one with the fewest moves.
+    #[arg(long)]
+    highest_score: bool,
+}
+
+#[derive(clap::Args)]
+struct ReconstructArgs {
+    /// The words played, in order. A word may be followed by `@` and a tile in move notation, e.g.
+    /// `QUIZ@C4`, to say it used that tile.
+    #[arg(required = true, value_parser = parse_reconstruct_word)]
+    words: Vec<reconstruct::Word>,
+
+    /// How many partial games to keep when a word can be traced more than one way.
+    #[arg(long, default_value_t = 50, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    beam_width: usize,
+
+    /// Write the solution to this file as JSON.
+    #[arg(long)]
+    save_solution: Option<std::path::PathBuf>,
+}
+
+fn parse_reconstruct_word(s: &str) -> anyhow::Result<reconstruct::Word> {
+    let (word, cell) = match s.split_once('@') {
+        Some((word, cell)) => (word, Some(notation::parse_cell(cell)?)),
+        None => (s, None),
+    };
+    Ok(reconstruct::Word {
+        word: word.to_ascii_uppercase(),
+        cell,
+    })
+}
+
+#[derive(clap::Args)]
+struct PlayedArgs {
+    /// The words played, in order, as for `reconstruct`.
+    #[arg(value_parser = parse_reconstruct_word, required_unless_present = "solution")]
+    words: Vec<reconstruct::Word>,
+
+    /// Read the moves from a JSON file written with `--save-solution` instead.
+    #[arg(long, conflicts_with = "words")]
+    solution: Option<std::path::PathBuf>,
+
+    /// How many partial games to keep when reconstructing from words.
+    #[arg(long, default_value_t = 50, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    beam_width: usize,
+}
+
+impl PlayedArgs {
+    fn load(
+        &self,
+        tower: &spelltower::Tower,
+        words: &words::Node,
+        rules: &spelltower::Rules,
+    ) -> anyhow::Result<spelltower::Solution> {
+        let Some(path) = &self.solution else {
+            return Ok(reconstruct::reconstruct(
+                tower,
+                words,
+                rules,
+                &self.words,
+                self.beam_width,
+            )?);
         };
-        delete_path(&mut tower, &best);
-        solution.push(best);
+
+        load_solution(path, tower, words, rules)
     }
 }
 
-fn solve_greedy(tower: &Tower, root: &words::Node) -> Vec<Vec<(usize, usize)>> {
-    let mut tower = tower.clone();
-    let mut solution = vec![];
+/// Reads a solution written with `--save-solution`, checking that every move is legal.
+fn load_solution(
+    path: &std::path::Path,
+    tower: &spelltower::Tower,
+    words: &words::Node,
+    rules: &spelltower::Rules,
+) -> anyhow::Result<spelltower::Solution> {
+    let solution: spelltower::Solution = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let mut game = game::Game::new(tower.clone(), words, *rules);
+    for (k, p) in solution.iter().enumerate() {
+        game.play(p)
+            .map_err(|e| anyhow::anyhow!("{}: move {}: {e}", path.display(), k + 1))?;
+    }
+    Ok(solution)
+}
 
-    loop {
-        let Some(best) = find_paths(&tower, root)
-            .into_iter()
-            .max_by_key(|path| score_path(&tower, &path))
-        else {
-            break;
-        };
-        delete_path(&mut tower, &best);
-        solution.push(best);
+fn save_solution(
+    path: &Option<std::path::PathBuf>,
+    solution: &spelltower::Solution,
+) -> anyhow::Result<()> {
+    if let Some(path) = path {
+        std::fs::write(path, serde_json::to_string(solution)?)?;
     }
+    Ok(())
+}
+
+#[derive(clap::Args)]
+struct AnalyzeArgs {
+    #[command(flatten)]
+    played: PlayedArgs,
+
+    /// How many of the highest-scoring alternatives to consider at each move.
+    #[arg(long, default_value_t = 20)]
+    candidates: usize,
 
-    solution
+    #[arg(long)]
+    json: bool,
 }
 
-fn pretty_tower(tower: &Tower, path: &[(usize, usize)]) -> String {
-    let (n, m) = tower.dim();
+#[cfg(feature = "render")]
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum ImageFormat {
+    Svg,
+    Png,
+}
 
-    #[derive(Clone, Copy, PartialEq)]
-    enum LinkType {
-        None,
-        Vertical,
-        Horizontal,
-        Diagonal,
-        Antidiagonal,
-        Cross,
-    }
+#[cfg(feature = "render")]
+#[derive(clap::Args)]
+struct RenderArgs {
+    #[command(flatten)]
+    played: PlayedArgs,
 
-    let mut links = ndarray::Array2::from_elem((n * 2 + 1, m * 2 + 1), LinkType::None);
-    let deletable: std::collections::HashSet<(usize, usize)> = deletable(tower, path);
+    /// The directory to write the images to.
+    #[arg(long, default_value = ".")]
+    out: std::path::PathBuf,
 
-    if !path.is_empty() {
-        for (&(ia, ja), &(ib, jb)) in path.iter().zip(path[1..].iter()) {
-            let li = ia * 2 + 1;
-            let lj = ja * 2 + 1;
+    #[arg(long, default_value = "svg")]
+    format: ImageFormat,
+}
 
-            let di = ib as isize - ia as isize;
-            let dj = jb as isize - ja as isize;
+#[derive(clap::Args)]
+struct ReportArgs {
+    #[command(flatten)]
+    played: PlayedArgs,
 
-            let l = &mut links[[(li as isize + di) as usize, (lj as isize + dj) as usize]];
-            match (di, dj) {
-                (-1, 0) | (1, 0) => {
-                    *l = LinkType::Vertical;
-                }
-                (0, -1) | (0, 1) => {
-                    *l = LinkType::Horizontal;
-                }
-                (-1, -1) | (1, 1) => {
-                    *l = if *l != LinkType::Antidiagonal {
-                        LinkType::Diagonal
-                    } else {
-                        LinkType::Cross
-                    };
-                }
-                (1, -1) | (-1, 1) => {
-                    *l = if *l != LinkType::Diagonal {
-                        LinkType::Antidiagonal
-                    } else {
-                        LinkType::Cross
-                    };
-                }
-                _ => unreachable!(),
-            }
+    /// The file to write the page to.
+    #[arg(long, short)]
+    out: std::path::PathBuf,
+}
+
+#[derive(clap::Args)]
+struct PlayAlongArgs {
+    #[command(flatten)]
+    played: PlayedArgs,
+
+    /// Show the tower before every move, not just at the start.
+    #[arg(long)]
+    boards: bool,
+}
+
+#[cfg(feature = "tui")]
+#[derive(clap::Args)]
+struct PlaybackArgs {
+    #[command(flatten)]
+    played: PlayedArgs,
+
+    /// How fast to play, e.g. 2 for twice as fast, from 0.05 to 20.
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Start paused, to step through the moves one at a time.
+    #[arg(long)]
+    paused: bool,
+}
+
+#[derive(clap::Args)]
+struct DiffArgs {
+    /// A JSON solution written with `--save-solution`.
+    a: std::path::PathBuf,
+
+    /// Another JSON solution for the same puzzle.
+    b: std::path::PathBuf,
+}
+
+#[derive(clap::Args)]
+struct DictArgs {
+    #[command(subcommand)]
+    query: DictQuery,
+
+    /// Show at most this many words.
+    #[arg(long, global = true)]
+    limit: Option<usize>,
+}
+
+#[derive(clap::Subcommand)]
+enum DictQuery {
+    /// Say whether each word is in the dictionary.
+    Check {
+        #[arg(required = true)]
+        words: Vec<String>,
+    },
+
+    /// List the words starting with a prefix.
+    Prefix { prefix: String },
+
+    /// List the words matching a pattern, where ? stands for any one letter and * for any number
+    /// of letters.
+    Match { pattern: String },
+
+    /// List the words that can be made from some of a set of letters, longest first.
+    Anagrams(AnagramsArgs),
+
+    /// List the longest words in the dictionary, or that can be made from a set of letters.
+    Longest(LettersArgs),
+}
+
+#[derive(clap::Args)]
+struct LettersArgs {
+    /// The letters to use, with ? for a wildcard.
+    letters: Option<String>,
+
+    /// Use the letters on the tower of the puzzle.
+    #[arg(long, conflicts_with = "letters")]
+    board: bool,
+}
+
+impl LettersArgs {
+    fn load(&self, puzzle_args: &PuzzleArgs) -> anyhow::Result<Option<lookup::Letters>> {
+        if self.board {
+            let tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
+            return Ok(Some(lookup::Letters::from_tower(&tower)));
         }
+        Ok(self
+            .letters
+            .as_deref()
+            .map(lookup::Letters::parse)
+            .transpose()?)
     }
+}
 
-    let mut pretty = ndarray::Array2::from_elem((n * 2 + 1, m * 2 + 1), " ".to_string());
+#[derive(clap::Args)]
+struct AnagramsArgs {
+    #[command(flatten)]
+    letters: LettersArgs,
 
-    for (i, j) in (0..n).flat_map(|i| (0..m).map(move |j| (i, j))) {
-        let pi = i * 2 + 1;
-        let pj = j * 2 + 1;
+    /// Only list words that use every letter.
+    #[arg(long)]
+    all: bool,
+}
 
-        for &(di, dj) in EIGHT_NEIGHBORS {
-            let li = (pi as isize + di) as usize;
-            let lj = (pj as isize + dj) as usize;
+#[derive(clap::Subcommand)]
+enum Command {
+    /// List every word that can be played on the puzzle.
+    Words(WordsArgs),
 
-            let link = links[[li, lj]];
-            pretty[[li, lj]] = match link {
-                LinkType::None => {
-                    if dj == 0 {
-                        "   "
-                    } else {
-                        " "
-                    }
-                }
-                LinkType::Vertical => "\x1b[1;35m │ \x1b[0m",
-                LinkType::Horizontal => "\x1b[1;35m─\x1b[0m",
-                LinkType::Diagonal => "\x1b[1;35m╲\x1b[0m",
-                LinkType::Antidiagonal => "\x1b[1;35m╱\x1b[0m",
-                LinkType::Cross => "\x1b[1;35m╳\x1b[0m",
+    /// Suggest the best next moves for the puzzle as it stands.
+    Hint(HintArgs),
+
+    /// Find moves that make a long word playable.
+    Plan(PlanArgs),
+
+    /// Work out the tiles used for a list of words played by hand, and score them.
+    Reconstruct(ReconstructArgs),
+
+    /// Find where a played game lost points against the solver.
+    Analyze(AnalyzeArgs),
+
+    /// Show where two solutions for the same puzzle diverge.
+    Diff(DiffArgs),
+
+    /// Draw every move of a solution as an image.
+    #[cfg(feature = "render")]
+    Render(RenderArgs),
+
+    /// Write an HTML page replaying a solution.
+    Report(ReportArgs),
+
+    /// List the moves of a solution in move notation, for entering them by hand.
+    PlayAlong(PlayAlongArgs),
+
+    /// Animate a solution in the terminal.
+    #[cfg(feature = "tui")]
+    Playback(PlaybackArgs),
+
+    /// Summarise a solution without giving it away, for sharing.
+    Share(PlayedArgs),
+
+    /// Play the puzzle interactively in the terminal.
+    #[cfg(feature = "tui")]
+    Tui,
+
+    /// Look words up in the dictionary.
+    Dict(DictArgs),
+
+    /// Speak a line-based protocol on stdin and stdout, for driving the solver from other programs.
+    Engine,
+
+    /// Answer solving requests over HTTP.
+    #[cfg(feature = "server")]
+    Serve(server::ServeArgs),
+}
+
+/// Solves the puzzle unless given a subcommand.
+#[derive(clap::Parser)]
+struct Args {
+    #[command(flatten)]
+    puzzle: PuzzleArgs,
+
+    #[command(flatten)]
+    style: StyleArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    solve: SolveArgs,
+}
+
+impl Args {
+    /// Parses the command line, rejecting solving options given along with a subcommand, which
+    /// would otherwise be ignored.
+    fn parse_checked() -> Args {
+        let matches = Args::command().get_matches();
+        let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+        if let Some((name, _)) = matches.subcommand() {
+            let solve = <SolveArgs as clap::Args>::augment_args(clap::Command::new("solve"));
+            let given = solve
+                .get_arguments()
+                .find(|arg| {
+                    matches.value_source(arg.get_id().as_str())
+                        == Some(clap::parser::ValueSource::CommandLine)
+                })
+                .and_then(|arg| arg.get_long());
+            if let Some(long) = given {
+                Args::command()
+                    .error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        format!("--{long} is for solving and cannot be used with '{name}'"),
+                    )
+                    .exit();
             }
-            .to_string();
         }
 
-        let c = match tower[[i, j]] {
-            '\0' => ' ',
-            '_' => '░',
-            v => v,
-        };
-        pretty[[pi, pj]] = if path.first() == Some(&(i, j)) {
-            format!("\x1b[1;37;45m {c} \x1b[0m")
-        } else if path.contains(&(i, j)) {
-            format!("\x1b[37;45m {c} \x1b[0m")
-        } else if deletable.contains(&(i, j)) {
-            format!("\x1b[35m {c} \x1b[0m")
-        } else {
-            format!(" {c} ")
-        };
+        args
     }
+}
 
-    let word = path.iter().map(|&(i, j)| tower[[i, j]]).collect::<String>();
-    let score = score_path(&tower, &path);
+fn main() -> anyhow::Result<()> {
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .init();
 
-    let border_length = (m - 1) + m * 3;
+    let args = Args::parse_checked();
+    let style = args.style.style();
 
-    let bottom_border = std::iter::repeat("═")
-        .take(border_length)
-        .collect::<String>();
+    match args.command {
+        None => solve(&args.puzzle, &args.solve, &style),
+        Some(Command::Words(words_args)) => list_words(&args.puzzle, &words_args),
+        Some(Command::Hint(hint_args)) => hint(&args.puzzle, &hint_args, &style),
+        Some(Command::Plan(plan_args)) => plan(&args.puzzle, &plan_args, &style),
+        Some(Command::Reconstruct(reconstruct_args)) => {
+            reconstruct(&args.puzzle, &reconstruct_args, &style)
+        }
+        Some(Command::Analyze(analyze_args)) => analyze(&args.puzzle, &analyze_args, &style),
+        Some(Command::Diff(diff_args)) => diff(&args.puzzle, &diff_args, &style),
+        #[cfg(feature = "render")]
+        Some(Command::Render(render_args)) => render(&args.puzzle, &render_args),
+        Some(Command::Report(report_args)) => report(&args.puzzle, &report_args),
+        #[cfg(feature = "tui")]
+        Some(Command::Playback(playback_args)) => {
+            let (words, _) = words::load();
+            let rules = args.puzzle.rules();
+            let tower = puzzle::parse(&args.puzzle.load()?.puzzle)?.tower;
+            let solution = playback_args.played.load(&tower, words, &rules)?;
+            playback::run(
+                tower,
+                &solution,
+                rules,
+                playback_args.speed,
+                playback_args.paused,
+                style,
+            )
+        }
+        Some(Command::Share(played_args)) => share(&args.puzzle, &played_args),
+        Some(Command::PlayAlong(play_along_args)) => {
+            play_along(&args.puzzle, &play_along_args, &style)
+        }
+        Some(Command::Dict(dict_args)) => dict(&args.puzzle, &dict_args),
+        #[cfg(feature = "tui")]
+        Some(Command::Tui) => {
+            let (words, _) = words::load();
+            let tower = puzzle::parse(&args.puzzle.load()?.puzzle)?.tower;
+            tui::run(tower, words, args.puzzle.rules(), style)
+        }
+        Some(Command::Engine) => {
+            let (words, _) = words::load();
+            engine::run(words, args.puzzle.rules())
+        }
+        #[cfg(feature = "server")]
+        Some(Command::Serve(serve_args)) => {
+            let (words, _) = words::load();
+            server::run(&serve_args, words)
+        }
+    }
+}
 
-    let top_border = if !path.is_empty() {
-        let header = format!("{word:} ({score:})");
-        format!("═{header:═<width$}", width = border_length - 1)
-    } else {
-        bottom_border.clone()
-    };
+fn list_words(puzzle_args: &PuzzleArgs, args: &WordsArgs) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
+
+    let containing = args
+        .containing
+        .as_deref()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
 
-    let body = pretty
-        .slice(ndarray::s![1..n * 2, 1..m * 2])
-        .rows()
+    let mut found = word_finder::find_words(&tower, words, &rules)
         .into_iter()
-        .map(|cols| {
-            format!(
-                "║{}║",
-                cols.into_iter().flat_map(|v| v.chars()).collect::<String>()
-            )
+        .filter(|w| {
+            args.min_length.is_none_or(|n| w.path.len() >= n)
+                && args.max_length.is_none_or(|n| w.path.len() <= n)
+                && args.min_score.is_none_or(|n| w.score >= n)
+                && word_finder::contains_letters(&w.word, &containing)
+                && args
+                    .cell
+                    .is_none_or(|cell| w.path.iter().any(|&(i, j, _)| (i, j) == cell))
         })
-        .collect::<Vec<_>>()
-        .join("\n");
+        .collect::<Vec<_>>();
+
+    found.sort_by(|a, b| {
+        match args.sort {
+            WordsSort::Score => b.score.cmp(&a.score),
+            WordsSort::Length => b.path.len().cmp(&a.path.len()),
+            WordsSort::Cleared => b.cleared.cmp(&a.cleared),
+            WordsSort::Word => std::cmp::Ordering::Equal,
+        }
+        .then_with(|| a.word.cmp(&b.word))
+    });
+    found.truncate(args.limit.unwrap_or(found.len()));
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&found)?);
+        return Ok(());
+    }
+
+    for w in found.iter() {
+        println!(
+            "{:<15} {:>6} {:>4} {}{} {}",
+            w.word,
+            w.score,
+            w.cleared,
+            if w.rare_letter_clear { 'R' } else { '-' },
+            if w.long_word_clear { 'L' } else { '-' },
+            notation::format(&tower, &w.path)
+        );
+    }
 
-    format!("╔{top_border}╗\n{body}\n╚{bottom_border}╝")
+    Ok(())
 }
 
-#[derive(clap::ValueEnum, Clone)]
-enum Coster {
-    TotalScore,
-    LongestWord,
-    BestWord,
-    FewestWords,
+fn hint(
+    puzzle_args: &PuzzleArgs,
+    args: &HintArgs,
+    style: &spelltower::Style,
+) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
+
+    for (k, hint) in hint::hints(&tower, words, &rules, args.n, args.candidates)
+        .into_iter()
+        .enumerate()
+    {
+        println!("{}", pretty_tower(&tower, &hint.path, &rules, style));
+        println!(
+            "#{}: {} now, {} with a greedy continuation",
+            k + 1,
+            hint.score,
+            hint.lookahead
+        );
+    }
+
+    Ok(())
 }
 
-impl Coster {
-    pub fn as_coster(&self) -> &'static annealers::Coster {
-        match self {
-            Coster::LongestWord => &annealers::LONGEST_WORD,
-            Coster::TotalScore => &annealers::TOTAL_SCORE,
-            Coster::BestWord => &annealers::BEST_WORD,
-            Coster::FewestWords => &annealers::FEWEST_WORDS,
+fn plan(
+    puzzle_args: &PuzzleArgs,
+    args: &PlanArgs,
+    style: &spelltower::Style,
+) -> anyhow::Result<()> {
+    let (words, max_len) = words::load();
+    let rules = puzzle_args.rules();
+    let tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
+
+    let goal = match &args.word {
+        Some(word) => {
+            let word = word.to_ascii_uppercase();
+            if !words.contains(&word) {
+                anyhow::bail!("{word} is not a word");
+            }
+            planner::Goal::Word(word)
         }
+        None => planner::Goal::Longest,
+    };
+
+    let Some(setup) = planner::plan(
+        &tower,
+        words,
+        &rules,
+        &goal,
+        if args.highest_score {
+            planner::Objective::HighestScore
+        } else {
+            planner::Objective::FewestMoves
+        },
+        &planner::Limits {
+            depth: args.depth,
+            beam_width: args.beam_width,
+            max_len,
+        },
+    ) else {
+        anyhow::bail!("no setup found within {} moves", args.depth);
+    };
+
+    let mut tower = tower;
+    for path in setup.moves.iter() {
+        println!("{}", pretty_tower(&tower, path, &rules, style));
+        delete_path(&mut tower, path);
     }
+    println!("{}", pretty_tower(&tower, &setup.target, &rules, style));
+
+    println!("TOTAL SCORE: {}", setup.score);
+
+    Ok(())
 }
 
-#[derive(clap::Parser)]
-struct Args {
-    #[arg(long, default_value = "total-score")]
-    coster: Coster,
+fn reconstruct(
+    puzzle_args: &PuzzleArgs,
+    args: &ReconstructArgs,
+    style: &spelltower::Style,
+) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
 
-    #[arg(long)]
-    day: Option<String>,
+    let solution = reconstruct::reconstruct(&tower, words, &rules, &args.words, args.beam_width)?;
 
-    #[arg(long)]
-    status: Option<String>,
+    {
+        let mut tower = tower.clone();
+        for path in solution.iter() {
+            println!("{}", pretty_tower(&tower, path, &rules, style));
+            delete_path(&mut tower, path);
+        }
+    }
 
-    #[arg(long)]
-    allow_leftovers: bool,
+    println!("TOTAL SCORE: {}", score_solution(&tower, &solution, &rules));
 
-    #[arg(long, default_value_t = 1000.0)]
-    initial_temperature: f64,
+    save_solution(&args.save_solution, &solution)
+}
 
-    #[arg(long, default_value = "spelltower")]
-    game: String,
+fn analyze(
+    puzzle_args: &PuzzleArgs,
+    args: &AnalyzeArgs,
+    style: &spelltower::Style,
+) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
+
+    let solution = args.played.load(&tower, words, &rules)?;
+    let moves = analysis::analyze(&tower, words, &rules, &solution, args.candidates);
+
+    let biggest_mistake = analysis::biggest_mistake(&moves);
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "moves": moves,
+                // Numbered from 1, like the moves in the printed analysis.
+                "biggest_mistake": biggest_mistake.map(|k| k + 1),
+            }))?
+        );
+        return Ok(());
+    }
 
-    #[arg(long, default_value_t = 5000)]
-    reannealing_fixed: u64,
+    let word = |path: &[(usize, usize, char)]| {
+        path.iter()
+            .map(|&(_, _, letter)| letter)
+            .collect::<String>()
+    };
+
+    for (k, m) in moves.iter().enumerate() {
+        println!(
+            "{:>3}. {:<15} {:>6} (worth {:>6})  best: {:<15} (worth {:>6})  regret: {}",
+            k + 1,
+            word(&m.path),
+            m.score,
+            m.value,
+            word(&m.best.path),
+            m.best.lookahead,
+            m.regret
+        );
+    }
+
+    if let Some(k) = biggest_mistake {
+        let m = &moves[k];
+        let mut tower = tower.clone();
+        for path in solution[..k].iter() {
+            delete_path(&mut tower, path);
+        }
+        println!();
+        println!("BIGGEST MISTAKE: move {}, {} points lost", k + 1, m.regret);
+        println!("{}", pretty_tower(&tower, &m.path, &rules, style));
+        println!("{}", pretty_tower(&tower, &m.best.path, &rules, style));
+    }
+
+    Ok(())
 }
 
-fn parse_puzzle(p: &str) -> anyhow::Result<crate::Tower> {
-    let mut puzzle_iter = p.split("\n");
-    let _ = puzzle_iter
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("no puzzle lines"))?;
+fn diff(
+    puzzle_args: &PuzzleArgs,
+    args: &DiffArgs,
+    style: &spelltower::Style,
+) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
 
-    let dim = puzzle_iter
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("no puzzle lines"))?;
+    let a = load_solution(&args.a, &tower, words, &rules)?;
+    let b = load_solution(&args.b, &tower, words, &rules)?;
 
-    let (w, h) = dim
-        .split_once("x")
-        .ok_or_else(|| anyhow::anyhow!("invalid dimensions: {dim}"))?;
+    print!(
+        "{}",
+        compare::compare(
+            &tower,
+            &a,
+            &b,
+            [&args.a.display().to_string(), &args.b.display().to_string()],
+            &rules,
+            style
+        )
+    );
 
-    let w: usize = w.parse()?;
-    let h: usize = h.parse()?;
+    Ok(())
+}
 
-    Ok(ndarray::Array2::from_shape_vec(
-        (h, w),
-        puzzle_iter
-            .flat_map(|row| row.chars())
-            .take(w * h)
-            .collect(),
-    )?)
+#[cfg(feature = "render")]
+fn render(puzzle_args: &PuzzleArgs, args: &RenderArgs) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let mut tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
+    let solution = args.played.load(&tower, words, &rules)?;
+
+    std::fs::create_dir_all(&args.out)?;
+    let write = |name: String, svg: String| -> anyhow::Result<()> {
+        let path = match args.format {
+            ImageFormat::Svg => {
+                let path = args.out.join(format!("{name}.svg"));
+                std::fs::write(&path, svg)?;
+                path
+            }
+            ImageFormat::Png => {
+                let path = args.out.join(format!("{name}.png"));
+                std::fs::write(&path, spelltower::render::png(&svg)?)?;
+                path
+            }
+        };
+        println!("{}", path.display());
+        Ok(())
+    };
+
+    for (k, path) in solution.iter().enumerate() {
+        let word = path.iter().map(|&(_, _, c)| c).collect::<String>();
+        write(
+            format!("{:02}-{word}", k + 1),
+            spelltower::render::svg(&tower, path, &rules),
+        )?;
+        delete_path(&mut tower, path);
+    }
+    write(
+        "end".to_string(),
+        spelltower::render::svg(&tower, &[], &rules),
+    )
 }
 
-fn main() -> anyhow::Result<()> {
-    env_logger::builder()
-        .filter_level(log::LevelFilter::Info)
-        .init();
+fn play_along(
+    puzzle_args: &PuzzleArgs,
+    args: &PlayAlongArgs,
+    style: &spelltower::Style,
+) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let mut tower = puzzle::parse(&puzzle_args.load()?.puzzle)?.tower;
+    let solution = args.played.load(&tower, words, &rules)?;
 
-    let args = Args::parse();
+    let style = spelltower::Style {
+        labels: true,
+        ..*style
+    };
+    if !args.boards {
+        println!("{}", pretty_tower(&tower, &[], &rules, &style));
+    }
 
+    let mut total = 0;
+    for (k, path) in solution.iter().enumerate() {
+        if args.boards {
+            println!("{}", pretty_tower(&tower, path, &rules, &style));
+        }
+        let score = spelltower::score_path(&tower, path, &rules);
+        total += score;
+        println!(
+            "{:>3}. {:<15} {:<40} {:>6} {:>6}",
+            k + 1,
+            path.iter().map(|&(_, _, c)| c).collect::<String>(),
+            notation::format(&tower, path),
+            score,
+            total
+        );
+        delete_path(&mut tower, path);
+    }
+
+    for bonus in spelltower::end_bonuses(&tower) {
+        println!("{bonus}: +{}", bonus.points());
+    }
+    println!("TOTAL SCORE: {}", total + spelltower::end_bonus(&tower));
+
+    Ok(())
+}
+
+fn share(puzzle_args: &PuzzleArgs, args: &PlayedArgs) -> anyhow::Result<()> {
     let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let puzzle = puzzle_args.load()?;
+    let tower = puzzle::parse(&puzzle.puzzle)?.tower;
+    let solution = args.load(&tower, words, &rules)?;
+
+    println!(
+        "{}",
+        spelltower::share::card(&report_title(&puzzle), &tower, &solution, &rules)
+    );
+    Ok(())
+}
 
-    let puzzle = puzzmo::load(
-        &args.game,
-        &args.status.as_ref().map(|v| v.as_str()).unwrap_or_else(|| {
-            if args.game == "cubeclear" {
-                "Experimental"
-            } else {
-                "Vanilla"
+fn dict(puzzle_args: &PuzzleArgs, args: &DictArgs) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+
+    let found = match &args.query {
+        DictQuery::Check { words: queries } => {
+            for query in queries.iter() {
+                let word = query.to_ascii_uppercase();
+                if words.contains(&word) {
+                    println!("{word} is a word");
+                } else {
+                    println!("{word} is not a word");
+                }
+            }
+            return Ok(());
+        }
+        DictQuery::Prefix { prefix } => lookup::with_prefix(words, prefix),
+        DictQuery::Match { pattern } => lookup::matching(words, pattern)?,
+        DictQuery::Anagrams(anagrams_args) => {
+            let letters = anagrams_args
+                .letters
+                .load(puzzle_args)?
+                .ok_or_else(|| anyhow::anyhow!("give some letters, or --board"))?;
+            let mut found = lookup::anagrams(words, &letters);
+            if anagrams_args.all {
+                found.retain(|word| word.len() == letters.len());
             }
-        }),
-        args.day,
+            found
+        }
+        DictQuery::Longest(letters_args) => {
+            let mut found = match letters_args.load(puzzle_args)? {
+                Some(letters) => lookup::anagrams(words, &letters),
+                None => lookup::with_prefix(words, ""),
+            };
+            let longest = found.iter().map(|word| word.len()).max().unwrap_or(0);
+            found.retain(|word| word.len() == longest);
+            found
+        }
+    };
+
+    for word in found.iter().take(args.limit.unwrap_or(usize::MAX)) {
+        println!("{word}");
+    }
+    Ok(())
+}
+
+fn report_title(puzzle: &puzzmo::Puzzle) -> String {
+    if puzzle.day.is_empty() {
+        "SpellTower".to_string()
+    } else {
+        format!("SpellTower {}", puzzle.day)
+    }
+}
+
+fn report(puzzle_args: &PuzzleArgs, args: &ReportArgs) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+    let puzzle = puzzle_args.load()?;
+    let tower = puzzle::parse(&puzzle.puzzle)?.tower;
+    let solution = args.played.load(&tower, words, &rules)?;
+
+    std::fs::write(
+        &args.out,
+        spelltower::report::html(&report_title(&puzzle), &tower, &solution, &rules),
     )?;
+    Ok(())
+}
+
+fn solve(
+    puzzle_args: &PuzzleArgs,
+    args: &SolveArgs,
+    style: &spelltower::Style,
+) -> anyhow::Result<()> {
+    let (words, _) = words::load();
+    let rules = puzzle_args.rules();
+
+    let puzzle = puzzle_args.load()?;
 
     log::info!(day = puzzle.day.as_str(), is_today = puzzle.is_today, coster = args.coster.to_possible_value().unwrap().get_name(); "spelltower solver");
 
-    let tower = parse_puzzle(&puzzle.puzzle)?;
-    println!("{}", pretty_tower(&tower, &[]));
+    let tower = puzzle::parse(&puzzle.puzzle)?.tower;
+    println!("{}", pretty_tower(&tower, &[], &rules, style));
 
     let solver =
         argmin::solver::simulatedannealing::SimulatedAnnealing::new(args.initial_temperature)?
@@ -501,7 +1082,8 @@ fn main() -> anyhow::Result<()> {
     let res = argmin::core::Executor::new(
         annealers::Annealer::new(
             &tower,
-            &words,
+            words,
+            rules,
             args.allow_leftovers,
             rand_xoshiro::Xoshiro256PlusPlus::from_entropy(),
             coster,
@@ -510,7 +1092,7 @@ fn main() -> anyhow::Result<()> {
     )
     .configure(|state| {
         state
-            .param(solve_greedy(&tower, &words))
+            .param(spelltower::solve_greedy(&tower, words, &rules))
             .target_cost(coster.target)
     })
     .add_observer(
@@ -525,12 +1107,28 @@ fn main() -> anyhow::Result<()> {
     {
         let mut tower = tower.clone();
         for path in solution.iter() {
-            println!("{}", pretty_tower(&tower, &path));
-            delete_path(&mut tower, &path);
+            println!("{}", pretty_tower(&tower, path, &rules, style));
+            if args.explain {
+                println!("{}", explain::explain(&tower, path, &rules));
+            }
+            delete_path(&mut tower, path);
+        }
+
+        if args.explain {
+            for bonus in spelltower::end_bonuses(&tower) {
+                println!("{bonus}: +{}", bonus.points());
+            }
         }
     }
 
-    println!("TOTAL SCORE: {}", score_solution(&tower, &solution));
+    println!("TOTAL SCORE: {}", score_solution(&tower, &solution, &rules));
 
-    Ok(())
+    if let Some(path) = &args.report {
+        std::fs::write(
+            path,
+            spelltower::report::html(&report_title(&puzzle), &tower, &solution, &rules),
+        )?;
+    }
+
+    save_solution(&args.save_solution, &solution)
 }
@@ -0,0 +1,122 @@
+//! A compact notation for moves, for people copying a solution onto a phone.
+//!
+//! Each tile is written as its column letter and row number, counting from `A1` at the top left
+//! the same way [`crate::pretty_tower`] labels the tower, and the tiles of a move are joined with
+//! `-`, e.g. `C4-D5-E5`. A wildcard is followed by `=` and the letter it is played as, e.g. `D5=E`;
+//! when parsing, that can be left off to play whichever letter scores best.
+
+use crate::{Path, Rules, Tile, Tower};
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("{0:?} is not a tile, expected e.g. C4")]
+    InvalidCell(String),
+
+    #[error("{0} does not spell a word")]
+    NotAWord(String),
+}
+
+/// The letters naming column `j`: `A` to `Z`, then `AA`, `AB` and so on.
+pub fn column_name(j: usize) -> String {
+    let mut name = vec![];
+    let mut j = j + 1;
+    while j > 0 {
+        name.push((b'A' + ((j - 1) % 26) as u8) as char);
+        j = (j - 1) / 26;
+    }
+    name.into_iter().rev().collect()
+}
+
+/// The name of the cell at `(row, column)`, e.g. `C4`.
+pub fn cell_name((i, j): (usize, usize)) -> String {
+    format!("{}{}", column_name(j), i + 1)
+}
+
+/// Writes `path`, played on `tower`, in move notation.
+pub fn format(tower: &Tower, path: &[(usize, usize, char)]) -> String {
+    path.iter()
+        .map(|&(i, j, letter)| {
+            if tower[[i, j]] == Tile::Wildcard {
+                format!("{}={letter}", cell_name((i, j)))
+            } else {
+                cell_name((i, j))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Reads a cell name such as `C4` as `(row, column)`.
+pub fn parse_cell(s: &str) -> Result<(usize, usize), Error> {
+    let invalid = || Error::InvalidCell(s.to_string());
+
+    let split = s
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .ok_or_else(invalid)?;
+    let (column, row) = s.split_at(split);
+    if column.is_empty() || !row.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let j = column
+        .bytes()
+        .try_fold(0usize, |j, b| {
+            j.checked_mul(26)?
+                .checked_add((b.to_ascii_uppercase() - b'A') as usize + 1)
+        })
+        .ok_or_else(invalid)?;
+    let i = row.parse::<usize>().map_err(|_| invalid())?;
+    if i == 0 {
+        return Err(invalid());
+    }
+
+    Ok((i - 1, j - 1))
+}
+
+/// A tile of a move as written down.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cell {
+    pub cell: (usize, usize),
+    /// The letter given for the tile, if any.
+    pub letter: Option<char>,
+}
+
+/// Reads a move as its tiles.
+pub fn parse(s: &str) -> Result<Vec<Cell>, Error> {
+    s.trim()
+        .split('-')
+        .map(|tile| {
+            let (cell, letter) = match tile.split_once('=') {
+                Some((cell, letter)) => {
+                    let mut chars = letter.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) if c.is_ascii_alphabetic() => {
+                            (cell, Some(c.to_ascii_uppercase()))
+                        }
+                        _ => return Err(Error::InvalidCell(tile.to_string())),
+                    }
+                }
+                None => (tile, None),
+            };
+            Ok(Cell {
+                cell: parse_cell(cell)?,
+                letter,
+            })
+        })
+        .collect()
+}
+
+/// Reads a move played on `tower`, choosing the highest-scoring letters for wildcards it doesn't
+/// give one for.
+pub fn parse_path(
+    tower: &Tower,
+    root: &crate::words::Node,
+    rules: &Rules,
+    s: &str,
+) -> Result<Path, Error> {
+    let tiles = parse(s)?;
+    let cells = tiles.iter().map(|t| t.cell).collect::<Vec<_>>();
+    let letters = tiles.iter().map(|t| t.letter).collect::<Vec<_>>();
+    crate::game::read_word_as(tower, root, rules, &cells, &letters)
+        .ok_or_else(|| Error::NotAWord(s.to_string()))
+}
@@ -0,0 +1,177 @@
+//! Planning moves that make a long word playable.
+
+use rayon::iter::{IntoParallelIterator as _, IntoParallelRefIterator as _, ParallelIterator as _};
+
+use crate::{Path, Rules, Solution, Tower};
+
+/// The word to plan for.
+pub enum Goal {
+    /// Spell this upper-case word.
+    Word(String),
+    /// Spell the longest word possible.
+    Longest,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Prefer setups with fewer preparatory moves.
+    FewestMoves,
+    /// Prefer setups scoring the most points in total.
+    HighestScore,
+}
+
+#[derive(Clone, Debug)]
+pub struct Setup {
+    /// The preparatory moves, in order.
+    pub moves: Solution,
+    /// The goal word, on the tower left by `moves`.
+    pub target: Path,
+    /// The points for the preparatory moves and the goal word.
+    pub score: usize,
+}
+
+/// How hard to look for a setup.
+pub struct Limits {
+    /// The most preparatory moves to try.
+    pub depth: usize,
+    /// How many positions to keep at each depth.
+    pub beam_width: usize,
+    /// The length of the longest word in the dictionary, past which there is no point looking for a
+    /// longer word.
+    pub max_len: usize,
+}
+
+struct State {
+    tower: Tower,
+    moves: Solution,
+    score: usize,
+    heuristic: usize,
+}
+
+fn spellable_prefix(tower: &Tower, word: &str) -> usize {
+    (1..=word.len())
+        .take_while(|&k| !crate::find_word_paths(tower, &word[..k]).is_empty())
+        .last()
+        .unwrap_or(0)
+}
+
+/// Searches breadth-first, keeping only the most promising positions at each depth, for moves after
+/// which `goal` can be played.
+pub fn plan(
+    tower: &Tower,
+    root: &crate::words::Node,
+    rules: &Rules,
+    goal: &Goal,
+    objective: Objective,
+    limits: &Limits,
+) -> Option<Setup> {
+    let better = |a: &Setup, b: &Setup| {
+        let key = |s: &Setup| {
+            let length = match goal {
+                Goal::Word(_) => 0,
+                Goal::Longest => s.target.len(),
+            };
+            match objective {
+                Objective::FewestMoves => (length, usize::MAX - s.moves.len(), s.score),
+                Objective::HighestScore => (length, s.score, usize::MAX - s.moves.len()),
+            }
+        };
+        key(a) > key(b)
+    };
+
+    let mut best: Option<Setup> = None;
+    let mut seen = std::collections::HashSet::from([tower.clone()]);
+    let mut frontier = vec![State {
+        tower: tower.clone(),
+        moves: vec![],
+        score: 0,
+        heuristic: 0,
+    }];
+
+    for depth in 0..=limits.depth {
+        let expanded = frontier
+            .into_par_iter()
+            .map(|state| {
+                let paths = crate::find_paths(&state.tower, root);
+
+                let target = match goal {
+                    Goal::Word(word) => crate::find_word_paths(&state.tower, word),
+                    Goal::Longest => paths.clone(),
+                }
+                .into_iter()
+                .map(|path| (crate::score_path(&state.tower, &path, rules), path))
+                .max_by_key(|(score, path)| (path.len(), *score))
+                .map(|(score, target)| Setup {
+                    moves: state.moves.clone(),
+                    target,
+                    score: state.score + score,
+                });
+
+                (state, paths, target)
+            })
+            .collect::<Vec<_>>();
+
+        for (_, _, setup) in expanded.iter() {
+            let Some(setup) = setup else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|best| better(setup, best)) {
+                best = Some(setup.clone());
+            }
+        }
+
+        let done = best.as_ref().is_some_and(|best| match goal {
+            Goal::Word(_) => objective == Objective::FewestMoves,
+            Goal::Longest => best.target.len() >= limits.max_len,
+        });
+        if done || depth == limits.depth {
+            break;
+        }
+
+        let mut children = expanded
+            .par_iter()
+            .flat_map(|(state, paths, _)| {
+                paths.par_iter().filter_map(move |path| {
+                    let mut tower = state.tower.clone();
+                    let score = crate::score_path(&tower, path, rules);
+                    crate::delete_path(&mut tower, path);
+
+                    let heuristic = match goal {
+                        Goal::Word(word) => {
+                            if !crate::has_letters(&tower, word) {
+                                return None;
+                            }
+                            spellable_prefix(&tower, word)
+                        }
+                        Goal::Longest => 0,
+                    };
+
+                    Some(State {
+                        moves: state
+                            .moves
+                            .iter()
+                            .cloned()
+                            .chain(std::iter::once(path.clone()))
+                            .collect(),
+                        score: state.score + score,
+                        tower,
+                        heuristic,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        children.sort_by_key(|c| std::cmp::Reverse((c.heuristic, c.score)));
+
+        frontier = vec![];
+        for child in children {
+            if frontier.len() >= limits.beam_width {
+                break;
+            }
+            if seen.insert(child.tower.clone()) {
+                frontier.push(child);
+            }
+        }
+    }
+
+    best
+}
@@ -0,0 +1,318 @@
+use crate::tui::{draw_links, paint_tile, screen_position, tile_char, title, Highlight};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    queue, style, terminal,
+};
+use spelltower::{Rules, Solution, Tile, Tower};
+
+/// How long each step of the animation takes at 1x speed.
+const FRAME: std::time::Duration = std::time::Duration::from_millis(60);
+
+/// The slowest and fastest playback goes, keeping every wait a duration we can sleep for.
+const MIN_SPEED: f64 = 0.05;
+const MAX_SPEED: f64 = 20.0;
+
+enum Control {
+    Continue,
+    /// Abandon the move being shown and go to this one instead.
+    Seek(usize),
+    Quit,
+}
+
+struct Player<'a> {
+    towers: Vec<Tower>,
+    solution: &'a Solution,
+    rules: Rules,
+    scores: Vec<usize>,
+    speed: f64,
+    paused: bool,
+    style: spelltower::Style,
+    /// Whether to pause again once the current move has finished, after stepping while paused.
+    stepping: bool,
+    current: usize,
+}
+
+pub fn run(
+    tower: Tower,
+    solution: &Solution,
+    rules: Rules,
+    speed: f64,
+    paused: bool,
+    style: spelltower::Style,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        (MIN_SPEED..=MAX_SPEED).contains(&speed),
+        "speed must be between {MIN_SPEED} and {MAX_SPEED}"
+    );
+
+    let mut towers = vec![tower];
+    for path in solution.iter() {
+        let mut tower = towers.last().unwrap().clone();
+        spelltower::delete_path(&mut tower, path);
+        towers.push(tower);
+    }
+
+    let _screen = crate::tui::Screen::enter(false)?;
+
+    Player {
+        scores: spelltower::cumulative_scores(&towers[0], solution, &rules),
+        towers,
+        solution,
+        rules,
+        speed,
+        paused,
+        style,
+        stepping: false,
+        current: 0,
+    }
+    .run(&mut std::io::stdout())
+}
+
+impl Player<'_> {
+    fn run(&mut self, out: &mut impl std::io::Write) -> anyhow::Result<()> {
+        loop {
+            let control = if self.current < self.solution.len() {
+                self.play_move(out)?
+            } else {
+                self.draw(out, &self.towers[self.current], &[], &[])?;
+                self.paused = true;
+                self.wait(out, FRAME)?
+            };
+
+            match control {
+                Control::Continue => self.current = (self.current + 1).min(self.solution.len()),
+                Control::Seek(k) => self.current = k,
+                Control::Quit => return Ok(()),
+            }
+            if self.stepping {
+                self.stepping = false;
+                self.paused = true;
+            }
+        }
+    }
+
+    /// Shows the path of the current move, flashes the tiles it clears and drops the rest column
+    /// by column.
+    fn play_move(&mut self, out: &mut impl std::io::Write) -> anyhow::Result<Control> {
+        let before = self.towers[self.current].clone();
+        let path = &self.solution[self.current];
+        let cells = path.iter().map(|&(i, j, _)| (i, j)).collect::<Vec<_>>();
+        let mut tower = before.clone();
+        let diff = spelltower::delete_path(&mut tower, path);
+        let cleared = diff.cleared.iter().map(|c| c.cell).collect::<Vec<_>>();
+
+        macro_rules! wait {
+            ($frames:expr) => {
+                match self.wait(out, FRAME * $frames)? {
+                    Control::Continue => {}
+                    control => return Ok(control),
+                }
+            };
+        }
+
+        self.draw(out, &before, &cells, &[])?;
+        wait!(12);
+
+        for flash in 0..4 {
+            self.draw(
+                out,
+                &before,
+                &cells,
+                if flash % 2 == 0 { &cleared } else { &[] },
+            )?;
+            wait!(2);
+        }
+
+        let mut frame = before.clone();
+        for &cell in cleared.iter() {
+            frame[cell] = Tile::Empty;
+        }
+        self.draw(out, &frame, &[], &[])?;
+        wait!(2);
+
+        let mut columns = diff.fallen.iter().map(|f| f.from.1).collect::<Vec<_>>();
+        columns.sort_unstable();
+        columns.dedup();
+        for column in columns {
+            let falls = diff
+                .fallen
+                .iter()
+                .filter(|f| f.from.1 == column)
+                .collect::<Vec<_>>();
+            let steps = falls.iter().map(|f| f.to.0 - f.from.0).max().unwrap_or(0);
+            for step in 1..=steps {
+                for fall in falls.iter() {
+                    let at = (fall.from.0 + step - 1).min(fall.to.0);
+                    frame[(at, column)] = Tile::Empty;
+                }
+                for fall in falls.iter() {
+                    let at = (fall.from.0 + step).min(fall.to.0);
+                    frame[(at, column)] = fall.tile;
+                }
+                self.draw(out, &frame, &[], &[])?;
+                wait!(1);
+            }
+        }
+
+        self.draw(out, &tower, &[], &[])?;
+        wait!(6);
+
+        Ok(Control::Continue)
+    }
+
+    /// Waits for `duration` at the current speed, or for as long as playback is paused, handling
+    /// key presses in the meantime.
+    fn wait(
+        &mut self,
+        out: &mut impl std::io::Write,
+        duration: std::time::Duration,
+    ) -> anyhow::Result<Control> {
+        let deadline = std::time::Instant::now() + duration.div_f64(self.speed);
+
+        loop {
+            let timeout = if self.paused {
+                None
+            } else {
+                let now = std::time::Instant::now();
+                if now >= deadline {
+                    return Ok(Control::Continue);
+                }
+                Some(deadline - now)
+            };
+
+            let ready = match timeout {
+                Some(timeout) => event::poll(timeout)?,
+                None => true,
+            };
+            if !ready {
+                continue;
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(Control::Quit),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(Control::Quit)
+                }
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('.') => {
+                    if self.paused {
+                        self.paused = false;
+                        self.stepping = true;
+                    } else {
+                        return Ok(Control::Seek((self.current + 1).min(self.solution.len())));
+                    }
+                }
+                KeyCode::Left | KeyCode::Char('h') | KeyCode::Char(',') => {
+                    return Ok(Control::Seek(self.current.saturating_sub(1)));
+                }
+                KeyCode::Home => return Ok(Control::Seek(0)),
+                KeyCode::End => return Ok(Control::Seek(self.solution.len())),
+                KeyCode::Char('+') | KeyCode::Char('=') => {
+                    self.speed = (self.speed * 1.5).min(MAX_SPEED)
+                }
+                KeyCode::Char('-') => self.speed = (self.speed / 1.5).max(MIN_SPEED),
+                _ => continue,
+            }
+            self.draw_status(out)?;
+            out.flush()?;
+        }
+    }
+
+    fn draw(
+        &self,
+        out: &mut impl std::io::Write,
+        tower: &Tower,
+        path: &[(usize, usize)],
+        flashing: &[(usize, usize)],
+    ) -> anyhow::Result<()> {
+        queue!(
+            out,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(screen_position((0, 0)).0, 0),
+            style::Print(title(&self.style))
+        )?;
+
+        let deletable = if path.is_empty() {
+            Default::default()
+        } else {
+            spelltower::deletable(tower, &self.solution[self.current])
+        };
+
+        for ((i, j), &tile) in tower.indexed_iter() {
+            let (x, y) = screen_position((i, j));
+            let c = match (tile, path.iter().position(|&c| c == (i, j))) {
+                (Tile::Wildcard, Some(k)) => self.solution[self.current][k].2.to_string(),
+                (tile, _) => tile_char(tile, &self.style),
+            };
+            let highlight = if flashing.contains(&(i, j)) || path.first() == Some(&(i, j)) {
+                Highlight::First
+            } else if path.contains(&(i, j)) {
+                Highlight::Path
+            } else if deletable.contains(&(i, j)) {
+                Highlight::Deletable
+            } else {
+                Highlight::None
+            };
+            queue!(
+                out,
+                cursor::MoveTo(x, y),
+                style::Print(paint_tile(c, highlight, false, &self.style))
+            )?;
+        }
+        draw_links(out, path, &self.style)?;
+
+        self.draw_status(out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    fn draw_status(&self, out: &mut impl std::io::Write) -> anyhow::Result<()> {
+        let (n, _) = self.towers[0].dim();
+        let (x, y) = screen_position((n, 0));
+
+        let progress = match self.solution.get(self.current) {
+            Some(path) => format!(
+                "move {}/{}: {} for {}, {} in total",
+                self.current + 1,
+                self.solution.len(),
+                path.iter().map(|&(_, _, c)| c).collect::<String>(),
+                spelltower::score_path(&self.towers[self.current], path, &self.rules),
+                self.scores[self.current]
+            ),
+            None => format!(
+                "final score: {}",
+                spelltower::score_solution(&self.towers[0], self.solution, &self.rules)
+            ),
+        };
+
+        for (k, line) in [
+            progress,
+            format!(
+                "speed {:.2}x{}",
+                self.speed,
+                if self.paused { "  paused" } else { "" }
+            ),
+            "space pause  right/l step  left/h back  home/end jump  +/- speed  q quit".to_string(),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            queue!(
+                out,
+                cursor::MoveTo(x, y + k as u16),
+                terminal::Clear(terminal::ClearType::CurrentLine),
+                cursor::MoveTo(x, y + k as u16),
+                style::Print(line)
+            )?;
+        }
+        Ok(())
+    }
+}
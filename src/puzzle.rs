@@ -0,0 +1,240 @@
+//! The Puzzmo puzzle text format.
+//!
+//! A puzzle looks like this:
+//!
+//! ```text
+//! SpellTower
+//! 3x2
+//! AbC
+//! D_?
+//! ```
+//!
+//! i.e. a title line, a `WxH` dimensions line, `H` rows of exactly `W` tiles each, and then any
+//! number of lines of metadata, which are `key: value` fields or free text. Everything else about
+//! the text, down to the line ending of each line, is kept around so that [`parse`] followed by
+//! writing the [`Puzzle`] back out with [`std::fmt::Display`] reproduces the input exactly.
+
+use crate::tile::Tile;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    /// The characters that end a line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// A line of metadata after the rows.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Metadata {
+    /// A `key: value` line.
+    Field { key: String, value: String },
+    /// Any other line, including blank ones, kept verbatim.
+    Text(String),
+}
+
+impl Metadata {
+    fn parse(line: &str) -> Metadata {
+        match line.split_once(": ") {
+            Some((key, value)) if !key.is_empty() && !key.contains(char::is_whitespace) => {
+                Metadata::Field {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                }
+            }
+            _ => Metadata::Text(line.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metadata::Field { key, value } => write!(f, "{key}: {value}"),
+            Metadata::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    pub title: String,
+    pub tower: crate::Tower,
+    pub metadata: Vec<Metadata>,
+    /// The ending of each line, in order. Lines past the end of this use the first ending, or
+    /// [`LineEnding::Lf`] if there is none.
+    pub line_endings: Vec<LineEnding>,
+    /// Whether the last line has a line ending too.
+    pub trailing_newline: bool,
+}
+
+impl Puzzle {
+    /// The value of the first metadata field called `key`.
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.metadata.iter().find_map(|line| match line {
+            Metadata::Field { key: k, value } if k == key => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    fn line_ending(&self, k: usize) -> LineEnding {
+        self.line_endings
+            .get(k)
+            .or(self.line_endings.first())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ErrorKind {
+    #[error("missing dimensions")]
+    MissingDimensions,
+
+    #[error("invalid dimensions {0:?}, expected WxH")]
+    InvalidDimensions(String),
+
+    #[error("expected {expected} rows, found {found}")]
+    MissingRows { expected: usize, found: usize },
+
+    #[error("expected {expected} tiles in row, found {found}")]
+    RaggedRow { expected: usize, found: usize },
+}
+
+/// An error in a puzzle, with 1-based line and column numbers.
+#[derive(thiserror::Error, Debug, PartialEq)]
+#[error("line {line}, column {column}: {kind}")]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+/// Reads a dimension, without leading zeros so that it is written back out the same way.
+fn parse_dimension(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0'))
+    {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Reads a puzzle in the Puzzmo text format.
+pub fn parse(p: &str) -> Result<Puzzle, Error> {
+    let mut lines = p.split('\n').collect::<Vec<_>>();
+
+    let trailing_newline = lines.len() > 1 && lines.last() == Some(&"");
+    if trailing_newline {
+        lines.pop();
+    }
+
+    // Only lines followed by a newline have an ending: a carriage return at the very end of the
+    // text is part of the last line.
+    let ended = if trailing_newline {
+        lines.len()
+    } else {
+        lines.len() - 1
+    };
+    let line_endings = lines[..ended]
+        .iter_mut()
+        .map(|line| match line.strip_suffix('\r') {
+            Some(stripped) => {
+                *line = stripped;
+                LineEnding::CrLf
+            }
+            None => LineEnding::Lf,
+        })
+        .collect();
+
+    let title = lines[0].to_string();
+
+    let dim = *lines.get(1).ok_or(Error {
+        line: 2,
+        column: 1,
+        kind: ErrorKind::MissingDimensions,
+    })?;
+
+    let invalid_dimensions = |column| Error {
+        line: 2,
+        column,
+        kind: ErrorKind::InvalidDimensions(dim.to_string()),
+    };
+
+    let (w, h) = dim.split_once('x').ok_or_else(|| invalid_dimensions(1))?;
+    let w = parse_dimension(w).ok_or_else(|| invalid_dimensions(1))?;
+    let h = parse_dimension(h).ok_or_else(|| invalid_dimensions(dim.find('x').unwrap() + 2))?;
+
+    let end = h.checked_add(2).ok_or_else(|| invalid_dimensions(1))?;
+    w.checked_mul(h).ok_or_else(|| invalid_dimensions(1))?;
+
+    let rows = lines.get(2..end).ok_or_else(|| Error {
+        line: lines.len() + 1,
+        column: 1,
+        kind: ErrorKind::MissingRows {
+            expected: h,
+            found: lines.len() - 2,
+        },
+    })?;
+
+    let mut tiles = vec![];
+    for (i, row) in rows.iter().enumerate() {
+        let found = row.chars().count();
+        if found != w {
+            return Err(Error {
+                line: i + 3,
+                column: found.min(w) + 1,
+                kind: ErrorKind::RaggedRow { expected: w, found },
+            });
+        }
+        tiles.extend(row.chars().map(Tile::from));
+    }
+
+    Ok(Puzzle {
+        title,
+        tower: ndarray::Array2::from_shape_vec((h, w), tiles).unwrap(),
+        metadata: lines[end..]
+            .iter()
+            .map(|line| Metadata::parse(line))
+            .collect(),
+        line_endings,
+        trailing_newline,
+    })
+}
+
+impl std::fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (h, w) = self.tower.dim();
+
+        let lines = [self.title.clone(), format!("{w}x{h}")]
+            .into_iter()
+            .chain(
+                self.tower
+                    .rows()
+                    .into_iter()
+                    .map(|row| row.iter().map(|&tile| char::from(tile)).collect()),
+            )
+            .chain(self.metadata.iter().map(|line| line.to_string()))
+            .collect::<Vec<_>>();
+
+        for (k, line) in lines.iter().enumerate() {
+            if k > 0 {
+                f.write_str(self.line_ending(k - 1).as_str())?;
+            }
+            f.write_str(line)?;
+        }
+        if self.trailing_newline {
+            f.write_str(self.line_ending(lines.len() - 1).as_str())?;
+        }
+
+        Ok(())
+    }
+}
@@ -80,6 +80,7 @@ struct Request {
     variables: std::collections::HashMap<String, serde_json::Value>,
 }
 
+/// A puzzle as Puzzmo serves it.
 pub struct Puzzle {
     pub day: String,
     pub is_today: bool,
@@ -87,12 +88,13 @@ pub struct Puzzle {
 }
 
 #[derive(thiserror::Error, Debug)]
-#[error("puzzmo: {errors:?}")]
+#[error("puzzmo: {}", errors.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join("; "))]
 pub struct Error {
-    pub errors: Vec<response::Error>,
+    errors: Vec<response::Error>,
 }
 
-pub fn load(game: &str, status: &str, day: Option<String>) -> anyhow::Result<Puzzle> {
+/// Fetches the puzzle for `game` with `status` from Puzzmo, for `day` or today.
+pub fn load(game: &str, status: &str, day: Option<String>) -> Result<Puzzle, crate::Error> {
     let client = reqwest::blocking::Client::new();
 
     let data = match client
@@ -119,7 +121,7 @@ pub fn load(game: &str, status: &str, day: Option<String>) -> anyhow::Result<Puz
         .into_iter()
         .find(|puzzle| puzzle.puzzle.game.slug == game && puzzle.status == status)
         .map(|puzzle| puzzle.puzzle.puzzle)
-        .ok_or_else(|| anyhow::anyhow!("could not find puzzle"))?;
+        .ok_or(crate::Error::PuzzleNotFound)?;
 
     Ok(Puzzle {
         day: data.today_page.daily.day,
@@ -0,0 +1,121 @@
+//! Working out which tiles were used from just the words a player remembers playing.
+
+use crate::{Rules, Solution, Tower};
+
+#[derive(Clone, Debug)]
+pub struct Word {
+    /// The upper-case word.
+    pub word: String,
+    /// A tile the word is known to have used.
+    pub cell: Option<(usize, usize)>,
+}
+
+/// Why the words could not be played. `index` counts from 0, but is shown counting from 1, and
+/// cells are shown in [`crate::notation`].
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("the beam width must be at least 1")]
+    ZeroBeamWidth,
+
+    #[error("word {} ({word}) is not in the dictionary", index + 1)]
+    NotAWord { index: usize, word: String },
+
+    #[error("word {} ({word}) needs letters that are no longer on the tower", index + 1)]
+    MissingLetters { index: usize, word: String },
+
+    #[error("word {} ({word}) cannot be traced through adjacent tiles", index + 1)]
+    NoPath { index: usize, word: String },
+
+    #[error("word {} ({word}) cannot be traced through {}", index + 1, crate::notation::cell_name(*cell))]
+    CellNotUsed {
+        index: usize,
+        word: String,
+        cell: (usize, usize),
+    },
+}
+
+/// Finds paths for `words`, played in order, keeping the `beam_width` highest-scoring partial
+/// games whenever a word can be traced more than one way. The returned solution is the one that
+/// scores the most by [`crate::score_solution`].
+pub fn reconstruct(
+    tower: &Tower,
+    root: &crate::words::Node,
+    rules: &Rules,
+    words: &[Word],
+    beam_width: usize,
+) -> Result<Solution, Error> {
+    if beam_width == 0 {
+        return Err(Error::ZeroBeamWidth);
+    }
+
+    let mut beam = vec![(tower.clone(), Solution::new(), 0)];
+
+    for (index, word) in words.iter().enumerate() {
+        if !root.contains(&word.word) {
+            return Err(Error::NotAWord {
+                index,
+                word: word.word.clone(),
+            });
+        }
+
+        let mut next = vec![];
+        for (tower, solution, score) in beam.iter() {
+            for path in crate::find_word_paths(tower, &word.word) {
+                if word
+                    .cell
+                    .is_some_and(|cell| !path.iter().any(|&(i, j, _)| (i, j) == cell))
+                {
+                    continue;
+                }
+
+                let mut tower = tower.clone();
+                let path_score = crate::score_path(&tower, &path, rules);
+                crate::delete_path(&mut tower, &path);
+
+                let mut solution = solution.clone();
+                solution.push(path);
+                next.push((tower, solution, score + path_score));
+            }
+        }
+
+        if next.is_empty() {
+            let (tower, _, _) = &beam[0];
+            let word = word.clone();
+            return Err(if !crate::has_letters(tower, &word.word) {
+                Error::MissingLetters {
+                    index,
+                    word: word.word,
+                }
+            } else if let Some(cell) = word.cell {
+                if crate::find_word_paths(tower, &word.word).is_empty() {
+                    Error::NoPath {
+                        index,
+                        word: word.word,
+                    }
+                } else {
+                    Error::CellNotUsed {
+                        index,
+                        word: word.word,
+                        cell,
+                    }
+                }
+            } else {
+                Error::NoPath {
+                    index,
+                    word: word.word,
+                }
+            });
+        }
+
+        next.sort_by_key(|(_, _, score)| std::cmp::Reverse(*score));
+        let mut seen = std::collections::HashSet::new();
+        next.retain(|(tower, _, _)| seen.insert(tower.clone()));
+        next.truncate(beam_width);
+        beam = next;
+    }
+
+    beam.into_iter()
+        .map(|(_, solution, _)| solution)
+        .max_by_key(|solution| crate::score_solution(tower, solution, rules))
+        .ok_or(Error::ZeroBeamWidth)
+}
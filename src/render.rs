@@ -0,0 +1,167 @@
+//! Drawing towers and moves as images, for pasting where [`crate::pretty_tower`]'s terminal output
+//! doesn't survive.
+
+use crate::{Rules, Tile, Tower};
+use resvg::{tiny_skia, usvg};
+
+const CELL: usize = 48;
+const GAP: usize = 4;
+const MARGIN: usize = 12;
+const HEADER: usize = 36;
+
+const FONT: &str = "DejaVu Sans, Arial, Helvetica, sans-serif";
+const HIGHLIGHT: &str = "#b0267f";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid SVG: {0}")]
+    Svg(#[from] usvg::Error),
+
+    #[error("image is too large")]
+    TooLarge,
+
+    #[error("failed to encode PNG: {0}")]
+    Encode(String),
+
+    #[error("no fonts are installed to draw the letters with")]
+    NoFonts,
+}
+
+fn center(i: usize, j: usize) -> (usize, usize) {
+    (
+        MARGIN + j * (CELL + GAP) + CELL / 2,
+        HEADER + MARGIN + i * (CELL + GAP) + CELL / 2,
+    )
+}
+
+/// Draws `tower` as an SVG image, with `path` and the tiles it would clear highlighted the same way
+/// as [`crate::pretty_tower`] does.
+pub fn svg(tower: &Tower, path: &[(usize, usize, char)], rules: &Rules) -> String {
+    let (n, m) = tower.dim();
+    let width = MARGIN * 2 + (m * (CELL + GAP)).saturating_sub(GAP);
+    let height = HEADER + MARGIN * 2 + (n * (CELL + GAP)).saturating_sub(GAP);
+    let deletable = crate::deletable(tower, path);
+
+    let mut out = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="{FONT}">
+<rect width="{width}" height="{height}" fill="#1d1b26"/>
+"##
+    );
+
+    if !path.is_empty() {
+        let word = path.iter().map(|&(_, _, c)| c).collect::<String>();
+        out += &format!(
+            r##"<text x="{MARGIN}" y="{}" font-size="20" font-weight="bold" fill="white">{word} <tspan fill="{HIGHLIGHT}">{}</tspan></text>
+"##,
+            HEADER - 8,
+            crate::score_path(tower, path, rules)
+        );
+    }
+
+    for ((i, j), &tile) in tower.indexed_iter() {
+        if tile.is_empty() {
+            continue;
+        }
+        let (x, y) = center(i, j);
+        let in_path = path.iter().any(|&(pi, pj, _)| (pi, pj) == (i, j));
+        let fill = match tile {
+            _ if in_path => HIGHLIGHT,
+            Tile::Blank => "#6b6878",
+            Tile::Letter { bonus: true, .. } => "#f2c84b",
+            _ => "#f3eee4",
+        };
+        let stroke = if deletable.contains(&(i, j)) && !in_path {
+            format!(r##" stroke="{HIGHLIGHT}" stroke-width="4""##)
+        } else {
+            String::new()
+        };
+        out += &format!(
+            r##"<rect x="{}" y="{}" width="{CELL}" height="{CELL}" rx="6" fill="{fill}"{stroke}/>
+"##,
+            x - CELL / 2,
+            y - CELL / 2
+        );
+    }
+
+    if path.len() > 1 {
+        let points = path
+            .iter()
+            .map(|&(i, j, _)| {
+                let (x, y) = center(i, j);
+                format!("{x},{y}")
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        out += &format!(
+            r##"<polyline points="{points}" fill="none" stroke="white" stroke-opacity="0.6" stroke-width="6" stroke-linecap="round" stroke-linejoin="round"/>
+"##
+        );
+    }
+
+    for ((i, j), &tile) in tower.indexed_iter() {
+        let letter = match path.iter().find(|&&(pi, pj, _)| (pi, pj) == (i, j)) {
+            Some(&(_, _, c)) => c,
+            None => match tile {
+                Tile::Empty | Tile::Blank => continue,
+                Tile::Letter { letter, .. } => letter,
+                tile => char::from(tile),
+            },
+        };
+        let (x, y) = center(i, j);
+        let weight = if path.first().is_some_and(|&(pi, pj, _)| (pi, pj) == (i, j)) {
+            "bold"
+        } else {
+            "normal"
+        };
+        let color = if path.iter().any(|&(pi, pj, _)| (pi, pj) == (i, j)) {
+            "white"
+        } else if deletable.contains(&(i, j)) {
+            HIGHLIGHT
+        } else {
+            "#1d1b26"
+        };
+        let letter = match letter {
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '&' => "&amp;".to_string(),
+            c => c.to_string(),
+        };
+        out += &format!(
+            r##"<text x="{x}" y="{}" font-size="26" font-weight="{weight}" text-anchor="middle" fill="{color}">{letter}</text>
+"##,
+            y + 9
+        );
+    }
+
+    out += "</svg>\n";
+    out
+}
+
+/// Rasterises an image made by [`svg`] to PNG, using the fonts installed on the system. Without
+/// any, the letters would silently be left out, so that is an error.
+pub fn png(svg: &str) -> Result<Vec<u8>, Error> {
+    static FONTS: std::sync::OnceLock<std::sync::Arc<usvg::fontdb::Database>> =
+        std::sync::OnceLock::new();
+
+    let fonts = FONTS.get_or_init(|| {
+        let mut fonts = usvg::fontdb::Database::new();
+        fonts.load_system_fonts();
+        std::sync::Arc::new(fonts)
+    });
+    if fonts.is_empty() {
+        return Err(Error::NoFonts);
+    }
+
+    let options = usvg::Options {
+        fontdb: fonts.clone(),
+        ..Default::default()
+    };
+
+    let tree = usvg::Tree::from_str(svg, &options)?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).ok_or(Error::TooLarge)?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap
+        .encode_png()
+        .map_err(|e| Error::Encode(e.to_string()))
+}
@@ -0,0 +1,317 @@
+//! A single-file HTML page replaying a solution move by move.
+
+use crate::{Rules, Tile, Tower};
+
+#[derive(serde::Serialize)]
+struct Move {
+    word: String,
+    score: usize,
+    total: usize,
+    path: Vec<(usize, usize)>,
+    cleared: Vec<(usize, usize)>,
+    /// `(from, to)` for every tile that falls.
+    fallen: Vec<((usize, usize), (usize, usize))>,
+    /// The tower before the move, one string per row in Puzzmo text format.
+    before: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+struct EndBonus {
+    name: String,
+    points: usize,
+}
+
+#[derive(serde::Serialize)]
+struct Replay {
+    title: String,
+    rows: usize,
+    cols: usize,
+    moves: Vec<Move>,
+    end: Vec<String>,
+    end_bonuses: Vec<EndBonus>,
+    score: usize,
+}
+
+fn rows(tower: &Tower) -> Vec<String> {
+    tower
+        .rows()
+        .into_iter()
+        .map(|row| row.iter().map(|&t| char::from(t)).collect())
+        .collect()
+}
+
+/// Writes a page that steps through `solution` on `tower`, with the path of each move highlighted,
+/// the cleared tiles flashing and the rest falling, along with the per-move and running scores and
+/// the end bonuses. Everything the page needs is inlined, so it can be shared as one file.
+pub fn html(title: &str, tower: &Tower, solution: &crate::Solution, rules: &Rules) -> String {
+    let (n, m) = tower.dim();
+    let mut tower = tower.clone();
+    let mut total = 0;
+
+    let moves = solution
+        .iter()
+        .map(|path| {
+            let before = rows(&tower);
+            let score = crate::score_path(&tower, path, rules);
+            total += score;
+            let diff = crate::delete_path(&mut tower, path);
+            Move {
+                word: path.iter().map(|&(_, _, c)| c).collect(),
+                score,
+                total,
+                path: path.iter().map(|&(i, j, _)| (i, j)).collect(),
+                cleared: diff.cleared.iter().map(|c| c.cell).collect(),
+                fallen: diff.fallen.iter().map(|f| (f.from, f.to)).collect(),
+                before,
+            }
+        })
+        .collect();
+
+    let end_bonuses = crate::end_bonuses(&tower)
+        .into_iter()
+        .map(|bonus| EndBonus {
+            name: bonus.to_string(),
+            points: bonus.points(),
+        })
+        .collect::<Vec<_>>();
+
+    let replay = Replay {
+        title: title.to_string(),
+        rows: n,
+        cols: m,
+        moves,
+        end: rows(&tower),
+        score: total + end_bonuses.iter().map(|b| b.points).sum::<usize>(),
+        end_bonuses,
+    };
+
+    // Nothing in the data may close the script element it is embedded in.
+    let data = serde_json::to_string(&replay)
+        .unwrap()
+        .replace("</", "<\\/");
+
+    fill(
+        TEMPLATE,
+        &[
+            ("{{title}}", &escape(title)),
+            ("{{blank}}", &char::from(Tile::Blank).to_string()),
+            ("{{data}}", &data),
+        ],
+    )
+}
+
+/// Replaces each placeholder in `template` with its value, in one pass so that placeholders in the
+/// values are left as they are.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut page = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        page.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values.iter().find(|(name, _)| rest.starts_with(name)) {
+            Some((name, value)) => {
+                page.push_str(value);
+                rest = &rest[name.len()..];
+            }
+            None => {
+                page.push_str("{{");
+                rest = &rest[2..];
+            }
+        }
+    }
+    page.push_str(rest);
+    page
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{title}}</title>
+<style>
+  :root { --cell: 44px; --gap: 4px; --highlight: #b0267f; }
+  body { margin: 0; padding: 16px; background: #1d1b26; color: #f3eee4; font-family: "DejaVu Sans", Arial, Helvetica, sans-serif; }
+  h1 { font-size: 20px; margin: 0 0 12px; }
+  main { display: flex; flex-wrap: wrap; gap: 24px; align-items: flex-start; }
+  #board { position: relative; }
+  .tile { position: absolute; width: var(--cell); height: var(--cell); border-radius: 6px; background: #f3eee4; color: #1d1b26;
+          display: flex; align-items: center; justify-content: center; font-size: 24px; box-sizing: border-box;
+          transition: top 0.35s ease-in, opacity 0.2s; }
+  .tile.bonus { background: #f2c84b; }
+  .tile.blank { background: #6b6878; }
+  .tile.path { background: var(--highlight); color: white; }
+  .tile.first { font-weight: bold; }
+  .tile.cleared:not(.path) { border: 4px solid var(--highlight); color: var(--highlight); }
+  .tile.flash { animation: flash 0.2s steps(1) 2; }
+  .tile.gone { opacity: 0; }
+  @keyframes flash { 50% { background: white; color: var(--highlight); } }
+  #controls { margin: 12px 0; display: flex; gap: 8px; }
+  button { background: #3a3648; color: inherit; border: none; border-radius: 4px; padding: 6px 12px; font: inherit; cursor: pointer; }
+  table { border-collapse: collapse; }
+  td, th { padding: 2px 10px; text-align: right; }
+  td:nth-child(2), th:nth-child(2) { text-align: left; }
+  tr.move { cursor: pointer; }
+  tr.current { background: var(--highlight); }
+  tfoot td { font-weight: bold; border-top: 1px solid #6b6878; }
+</style>
+</head>
+<body>
+<h1>{{title}}</h1>
+<main>
+  <div>
+    <div id="board"></div>
+    <div id="controls">
+      <button id="start">&laquo;</button>
+      <button id="prev">&lsaquo;</button>
+      <button id="play">play</button>
+      <button id="next">&rsaquo;</button>
+      <button id="end">&raquo;</button>
+    </div>
+    <div id="status"></div>
+  </div>
+  <table>
+    <thead><tr><th>#</th><th>word</th><th>score</th><th>total</th></tr></thead>
+    <tbody id="moves"></tbody>
+    <tfoot id="bonuses"></tfoot>
+  </table>
+</main>
+<script id="replay" type="application/json">{{data}}</script>
+<script>
+"use strict";
+const replay = JSON.parse(document.getElementById("replay").textContent);
+const board = document.getElementById("board");
+const cell = 44, gap = 4;
+const position = (i, j) => [j * (cell + gap), i * (cell + gap)];
+board.style.width = (replay.cols * (cell + gap) - gap) + "px";
+board.style.height = (replay.rows * (cell + gap) - gap) + "px";
+
+let step = 0;
+let tiles = new Map();
+let busy = false;
+let playing = null;
+
+function draw(rows) {
+  board.textContent = "";
+  tiles = new Map();
+  rows.forEach((row, i) => [...row].forEach((c, j) => {
+    if (c === " ") return;
+    const tile = document.createElement("div");
+    tile.className = "tile";
+    if (c === "{{blank}}") tile.classList.add("blank");
+    else {
+      if (c !== c.toUpperCase()) tile.classList.add("bonus");
+      tile.textContent = c.toUpperCase();
+    }
+    const [x, y] = position(i, j);
+    tile.style.left = x + "px";
+    tile.style.top = y + "px";
+    board.appendChild(tile);
+    tiles.set(i + "," + j, tile);
+  }));
+}
+
+function highlight(move) {
+  move.cleared.forEach(([i, j]) => tiles.get(i + "," + j).classList.add("cleared"));
+  move.path.forEach(([i, j], k) => {
+    const tile = tiles.get(i + "," + j);
+    tile.classList.add("path");
+    if (k === 0) tile.classList.add("first");
+    tile.textContent = move.word[k];
+  });
+}
+
+function show(k) {
+  step = k;
+  if (k < replay.moves.length) {
+    draw(replay.moves[k].before);
+    highlight(replay.moves[k]);
+  } else {
+    draw(replay.end);
+  }
+  document.querySelectorAll("tr.move").forEach((tr, m) => tr.classList.toggle("current", m === k));
+  const total = k === 0 ? 0 : replay.moves[k - 1].total;
+  document.getElementById("status").textContent = k < replay.moves.length
+    ? `move ${k + 1} of ${replay.moves.length}: ${replay.moves[k].word} for ${replay.moves[k].score}, ${total} so far`
+    : `final score ${replay.score}`;
+}
+
+function advance() {
+  if (busy || step >= replay.moves.length) return Promise.resolve();
+  busy = true;
+  const move = replay.moves[step];
+  const wait = ms => new Promise(resolve => setTimeout(resolve, ms));
+  move.cleared.forEach(([i, j]) => tiles.get(i + "," + j).classList.add("flash"));
+  return wait(450).then(() => {
+    move.cleared.forEach(([i, j]) => tiles.get(i + "," + j).classList.add("gone"));
+    return wait(200);
+  }).then(() => {
+    const moved = move.fallen.map(([from, to]) => [tiles.get(from.join(",")), to]);
+    moved.forEach(([tile, [i, j]]) => { tile.style.top = position(i, j)[1] + "px"; });
+    return wait(400);
+  }).then(() => {
+    busy = false;
+    show(step + 1);
+  });
+}
+
+function stop() {
+  clearTimeout(playing);
+  playing = null;
+  document.getElementById("play").textContent = "play";
+}
+
+function play() {
+  if (playing !== null || step >= replay.moves.length) return stop();
+  document.getElementById("play").textContent = "pause";
+  const loop = () => advance().then(() => {
+    if (playing === null) return;
+    if (step >= replay.moves.length) return stop();
+    playing = setTimeout(loop, 800);
+  });
+  playing = setTimeout(loop, 0);
+}
+
+const moves = document.getElementById("moves");
+replay.moves.forEach((move, k) => {
+  const tr = document.createElement("tr");
+  tr.className = "move";
+  [k + 1, move.word, move.score, move.total].forEach(v => {
+    const td = document.createElement("td");
+    td.textContent = v;
+    tr.appendChild(td);
+  });
+  tr.addEventListener("click", () => { stop(); show(k); });
+  moves.appendChild(tr);
+});
+const bonuses = document.getElementById("bonuses");
+[...replay.end_bonuses.map(b => [b.name, "+" + b.points]), ["total", replay.score]].forEach(([name, points]) => {
+  const tr = document.createElement("tr");
+  tr.innerHTML = "<td></td><td></td><td></td><td></td>";
+  tr.children[1].textContent = name;
+  tr.children[3].textContent = points;
+  bonuses.appendChild(tr);
+});
+
+document.getElementById("start").onclick = () => { stop(); show(0); };
+document.getElementById("prev").onclick = () => { stop(); if (!busy && step > 0) show(step - 1); };
+document.getElementById("next").onclick = () => { stop(); advance(); };
+document.getElementById("end").onclick = () => { stop(); show(replay.moves.length); };
+document.getElementById("play").onclick = play;
+document.addEventListener("keydown", e => {
+  if (e.key === "ArrowRight") document.getElementById("next").click();
+  else if (e.key === "ArrowLeft") document.getElementById("prev").click();
+  else if (e.key === " ") { e.preventDefault(); play(); }
+});
+
+show(0);
+</script>
+</body>
+</html>
+"##;
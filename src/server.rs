@@ -0,0 +1,542 @@
+//! A small HTTP server answering solver requests with JSON.
+//!
+//! Every endpoint takes a `POST` of either a puzzle in Puzzmo text format, or a JSON object whose
+//! `puzzle` is the Puzzmo text or an array of rows, plus any of the options below:
+//!
+//! - `/solve`: `coster`, `allow_leftovers`, `time_limit_ms`
+//! - `/hint`: `n`, `candidates`, looking ahead from as many of the candidates as it can in time
+//! - `/verify`: `solution`, as written by `--save-solution`
+//! - `/words`: `limit`
+//!
+//! `wildcard_scoring` and `time_limit_ms` may be given to any of them. Requests are handed to a
+//! fixed number of workers through a bounded queue, and turned away with 503 when it is full, so
+//! that a burst of requests doesn't pile work onto the rayon pool every search shares. A request
+//! still working when its time is up is answered with 503, though its worker stays busy until it
+//! finishes; one that panics is answered with 500.
+
+use rand::SeedableRng as _;
+use spelltower::{annealers, explain, game, hint, puzzle, word_finder, words, Rules, Solution};
+use std::io::Read as _;
+
+#[derive(clap::Args)]
+pub struct ServeArgs {
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    address: String,
+
+    /// How many requests to work on at once.
+    #[arg(long, default_value_t = 1)]
+    workers: usize,
+
+    /// How many requests may wait for a worker before new ones are turned away.
+    #[arg(long, default_value_t = 16)]
+    queue: usize,
+
+    /// How long to search for a solution if the request doesn't say.
+    #[arg(long, default_value_t = 5000)]
+    time_limit_ms: u64,
+
+    /// The longest a request may ask to search for.
+    #[arg(long, default_value_t = 60000)]
+    max_time_limit_ms: u64,
+
+    /// The largest request body to read; larger ones are turned away with 413.
+    #[arg(long, default_value_t = 1 << 20)]
+    max_request_bytes: u64,
+}
+
+/// How long past its time limit a request has to write up its answer before it is given up on.
+const GRACE: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PuzzleText {
+    Text(String),
+    Rows(Vec<String>),
+}
+
+#[derive(serde::Deserialize)]
+struct Request {
+    puzzle: PuzzleText,
+    wildcard_scoring: Option<crate::WildcardScoring>,
+
+    coster: Option<crate::Coster>,
+    #[serde(default)]
+    allow_leftovers: bool,
+    time_limit_ms: Option<u64>,
+
+    n: Option<usize>,
+    candidates: Option<usize>,
+
+    solution: Option<Solution>,
+
+    limit: Option<usize>,
+}
+
+impl Request {
+    fn parse(body: &str) -> anyhow::Result<Self> {
+        if body.trim_start().starts_with('{') {
+            return Ok(serde_json::from_str(body)?);
+        }
+        Ok(Request {
+            puzzle: PuzzleText::Text(body.to_string()),
+            wildcard_scoring: None,
+            coster: None,
+            allow_leftovers: false,
+            time_limit_ms: None,
+            n: None,
+            candidates: None,
+            solution: None,
+            limit: None,
+        })
+    }
+
+    fn tower(&self) -> anyhow::Result<spelltower::Tower> {
+        let text = match &self.puzzle {
+            PuzzleText::Text(text) => text.clone(),
+            PuzzleText::Rows(rows) => format!(
+                "SpellTower\n{}x{}\n{}\n",
+                rows.first().map_or(0, |row| row.chars().count()),
+                rows.len(),
+                rows.join("\n")
+            ),
+        };
+        Ok(puzzle::parse(&text)?.tower)
+    }
+
+    fn rules(&self) -> Rules {
+        Rules {
+            wildcard_scoring: self
+                .wildcard_scoring
+                .as_ref()
+                .map_or_else(Default::default, |w| w.as_wildcard_scoring()),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Move {
+    breakdown: explain::Breakdown,
+    frame: String,
+}
+
+#[derive(serde::Serialize)]
+struct EndBonus {
+    bonus: spelltower::EndBonus,
+    points: usize,
+}
+
+/// Plays out `solution`, explaining and drawing each move.
+fn replay(
+    tower: &spelltower::Tower,
+    solution: &Solution,
+    rules: &Rules,
+) -> (Vec<Move>, Vec<EndBonus>) {
+    let mut tower = tower.clone();
+    let moves = solution
+        .iter()
+        .map(|path| {
+            let m = Move {
+                breakdown: explain::explain(&tower, path, rules),
+                frame: spelltower::pretty_tower(&tower, path, rules, &spelltower::Style::PLAIN),
+            };
+            spelltower::delete_path(&mut tower, path);
+            m
+        })
+        .collect();
+    let bonuses = spelltower::end_bonuses(&tower)
+        .into_iter()
+        .map(|bonus| EndBonus {
+            bonus,
+            points: bonus.points(),
+        })
+        .collect();
+    (moves, bonuses)
+}
+
+/// A parsed request, ready to be worked on away from the worker that received it.
+struct Job {
+    root: &'static words::Node,
+    request: Request,
+    started: std::time::Instant,
+    time_limit: std::time::Duration,
+}
+
+impl Job {
+    fn solve(&self) -> anyhow::Result<serde_json::Value> {
+        let request = &self.request;
+        let tower = request.tower()?;
+        let rules = request.rules();
+        let coster = request
+            .coster
+            .as_ref()
+            .unwrap_or(&crate::Coster::TotalScore)
+            .as_coster();
+
+        // The annealer needs a move to start from.
+        let greedy = spelltower::solve_greedy(&tower, self.root, &rules);
+        let solution = if greedy.is_empty() {
+            greedy
+        } else {
+            let res = argmin::core::Executor::new(
+                annealers::Annealer::new(
+                    &tower,
+                    self.root,
+                    rules,
+                    request.allow_leftovers,
+                    rand_xoshiro::Xoshiro256PlusPlus::from_entropy(),
+                    coster,
+                ),
+                argmin::solver::simulatedannealing::SimulatedAnnealing::new(1000.0)?
+                    .with_reannealing_fixed(5000),
+            )
+            .configure(|state| state.param(greedy).target_cost(coster.target))
+            .ctrlc(false)
+            .timeout(self.time_limit)
+            .run()?;
+            res.state.best_param.unwrap_or_default()
+        };
+
+        let (moves, end_bonuses) = replay(&tower, &solution, &rules);
+        Ok(serde_json::json!({
+            "score": spelltower::score_solution(&tower, &solution, &rules),
+            "solution": solution,
+            "moves": moves,
+            "end_bonuses": end_bonuses,
+        }))
+    }
+
+    fn hint(&self) -> anyhow::Result<serde_json::Value> {
+        let request = &self.request;
+        let tower = request.tower()?;
+        let rules = request.rules();
+        let n = request.n.unwrap_or(5);
+
+        let hints = hint::hints_until(
+            &tower,
+            self.root,
+            &rules,
+            n,
+            request.candidates.unwrap_or(20).max(n),
+            Some(self.started + self.time_limit),
+        )
+        .into_iter()
+        .map(|hint| {
+            let frame =
+                spelltower::pretty_tower(&tower, &hint.path, &rules, &spelltower::Style::PLAIN);
+            serde_json::json!({ "hint": hint, "frame": frame })
+        })
+        .collect::<Vec<_>>();
+        Ok(serde_json::json!({ "hints": hints }))
+    }
+
+    fn verify(&self) -> anyhow::Result<serde_json::Value> {
+        let request = &self.request;
+        let tower = request.tower()?;
+        let rules = request.rules();
+        let solution = request
+            .solution
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("missing solution"))?;
+
+        let mut game = game::Game::new(tower.clone(), self.root, rules);
+        for (k, path) in solution.iter().enumerate() {
+            if let Err(e) = game.play(path) {
+                return Ok(serde_json::json!({
+                    "valid": false,
+                    "move": k + 1,
+                    "error": e.to_string(),
+                }));
+            }
+        }
+
+        let (moves, end_bonuses) = replay(&tower, solution, &rules);
+        Ok(serde_json::json!({
+            "valid": true,
+            "score": spelltower::score_solution(&tower, solution, &rules),
+            "moves": moves,
+            "end_bonuses": end_bonuses,
+        }))
+    }
+
+    fn words(&self) -> anyhow::Result<serde_json::Value> {
+        let request = &self.request;
+        let tower = request.tower()?;
+        let rules = request.rules();
+
+        let mut found = word_finder::find_words(&tower, self.root, &rules);
+        found.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.word.cmp(&b.word)));
+        found.truncate(request.limit.unwrap_or(found.len()));
+        Ok(serde_json::json!({ "words": found }))
+    }
+}
+
+struct Server<'a> {
+    args: &'a ServeArgs,
+    root: &'static words::Node,
+}
+
+impl Server<'_> {
+    fn handle(&self, mut request: tiny_http::Request) -> std::io::Result<()> {
+        let started = std::time::Instant::now();
+        let method = request.method().clone();
+        let url = request.url().to_string();
+        let (status, response, job) = self.answer(&method, &url, request.as_reader());
+        log::info!(url = url.as_str(), status, elapsed_ms = started.elapsed().as_millis() as u64; "request");
+        let result = respond(request, status, &response);
+
+        // Don't take on more work until a request that overran has finished.
+        if let Some(job) = job {
+            let _ = job.join();
+        }
+        result
+    }
+
+    /// Answers a `method` request for `url` with `body`, returning the status and JSON to respond
+    /// with, and the thread it was worked on, if it got that far.
+    fn answer(
+        &self,
+        method: &tiny_http::Method,
+        url: &str,
+        body: impl std::io::Read,
+    ) -> (u16, serde_json::Value, Option<std::thread::JoinHandle<()>>) {
+        let endpoint = match url.split('?').next().unwrap_or_default() {
+            "/solve" => Job::solve,
+            "/hint" => Job::hint,
+            "/verify" => Job::verify,
+            "/words" => Job::words,
+            _ => return (404, error("no such endpoint"), None),
+        };
+        if *method != tiny_http::Method::Post {
+            return (405, error("expected POST"), None);
+        }
+
+        let mut bytes = vec![];
+        let limit = self.args.max_request_bytes;
+        if let Err(e) = body.take(limit + 1).read_to_end(&mut bytes) {
+            return (400, error(e), None);
+        }
+        if bytes.len() as u64 > limit {
+            return (
+                413,
+                error(format!("bodies are limited to {limit} bytes")),
+                None,
+            );
+        }
+
+        let parsed = String::from_utf8(bytes)
+            .map_err(anyhow::Error::from)
+            .and_then(|body| Request::parse(&body));
+        match parsed {
+            Ok(parsed) => {
+                let (status, response, job) = self.run(endpoint, parsed);
+                (status, response, Some(job))
+            }
+            Err(e) => (400, error(e), None),
+        }
+    }
+
+    /// Works on `request` on a thread of its own, so that it can be answered when its time is up
+    /// or it panics, returning the answer and the thread.
+    fn run(
+        &self,
+        endpoint: fn(&Job) -> anyhow::Result<serde_json::Value>,
+        request: Request,
+    ) -> (u16, serde_json::Value, std::thread::JoinHandle<()>) {
+        let time_limit_ms = request
+            .time_limit_ms
+            .unwrap_or(self.args.time_limit_ms)
+            .min(self.args.max_time_limit_ms);
+        let job = Job {
+            root: self.root,
+            request,
+            started: std::time::Instant::now(),
+            time_limit: std::time::Duration::from_millis(time_limit_ms),
+        };
+        let deadline = job.time_limit + GRACE;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let job = std::thread::spawn(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| endpoint(&job)));
+            let _ = tx.send(result);
+        });
+
+        let (status, response) = match rx.recv_timeout(deadline) {
+            Ok(Ok(Ok(response))) => (200, response),
+            Ok(Ok(Err(e))) => (400, error(e)),
+            Ok(Err(_)) | Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                (500, error("internal error"))
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => (
+                503,
+                error(format!("not finished within {time_limit_ms} ms")),
+            ),
+        };
+        (status, response, job)
+    }
+}
+
+fn error(e: impl std::fmt::Display) -> serde_json::Value {
+    serde_json::json!({ "error": e.to_string() })
+}
+
+fn respond(
+    request: tiny_http::Request,
+    status: u16,
+    body: &serde_json::Value,
+) -> std::io::Result<()> {
+    request.respond(
+        tiny_http::Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(
+                tiny_http::Header::from_bytes("Content-Type", "application/json").unwrap(),
+            ),
+    )
+}
+
+pub fn run(args: &ServeArgs, root: &'static words::Node) -> anyhow::Result<()> {
+    let http = tiny_http::Server::http(&args.address).map_err(|e| anyhow::anyhow!(e))?;
+    log::info!(address = args.address.as_str(), workers = args.workers; "listening");
+
+    let (tx, rx) = std::sync::mpsc::sync_channel::<tiny_http::Request>(args.queue);
+    let rx = std::sync::Mutex::new(rx);
+    let server = Server { args, root };
+
+    std::thread::scope(|scope| {
+        for _ in 0..args.workers.max(1) {
+            scope.spawn(|| loop {
+                let Ok(request) = rx.lock().unwrap().recv() else {
+                    return;
+                };
+                if let Err(e) = server.handle(request) {
+                    log::warn!("failed to respond: {e}");
+                }
+            });
+        }
+
+        for request in http.incoming_requests() {
+            if let Err(std::sync::mpsc::TrySendError::Full(request)) = tx.try_send(request) {
+                if let Err(e) = respond(request, 503, &error("too many requests queued")) {
+                    log::warn!("failed to respond: {e}");
+                }
+            }
+        }
+        drop(tx);
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARGS: ServeArgs = ServeArgs {
+        address: String::new(),
+        workers: 1,
+        queue: 1,
+        time_limit_ms: 100,
+        max_time_limit_ms: 1000,
+        max_request_bytes: 1000,
+    };
+
+    fn answer(method: tiny_http::Method, url: &str, body: &str) -> (u16, serde_json::Value) {
+        let (root, _) = words::load();
+        let server = Server { args: &ARGS, root };
+        let (status, response, job) = server.answer(&method, url, body.as_bytes());
+        if let Some(job) = job {
+            job.join().unwrap();
+        }
+        (status, response)
+    }
+
+    fn post(url: &str, body: &str) -> (u16, serde_json::Value) {
+        answer(tiny_http::Method::Post, url, body)
+    }
+
+    #[test]
+    fn parses_text_and_json() {
+        let text = Request::parse("SpellTower\n3x1\nCAT\n").unwrap();
+        assert!(matches!(text.puzzle, PuzzleText::Text(ref text) if text.ends_with("CAT\n")));
+        assert!(text.solution.is_none());
+
+        let json =
+            Request::parse(r#" {"puzzle": ["CAT"], "n": 3, "allow_leftovers": true}"#).unwrap();
+        assert!(matches!(json.puzzle, PuzzleText::Rows(ref rows) if rows == &["CAT"]));
+        assert_eq!(json.n, Some(3));
+        assert!(json.allow_leftovers);
+
+        assert!(Request::parse(r#"{"n": 3}"#).is_err());
+        assert!(Request::parse(r#"{"puzzle": ["CAT"], "n": -1}"#).is_err());
+    }
+
+    #[test]
+    fn reads_towers_from_rows_or_text() {
+        let rows = Request::parse(r#"{"puzzle": ["DOG", "CAT"]}"#).unwrap();
+        let text = Request::parse("SpellTower\n3x2\nDOG\nCAT\n").unwrap();
+        assert_eq!(rows.tower().unwrap(), text.tower().unwrap());
+        assert_eq!(rows.tower().unwrap().dim(), (2, 3));
+
+        let ragged = Request::parse(r#"{"puzzle": ["DOG", "CATS"]}"#).unwrap();
+        assert!(ragged.tower().is_err());
+    }
+
+    #[test]
+    fn answers_requests() {
+        let (status, response) = post("/words", r#"{"puzzle": ["CAT"]}"#);
+        assert_eq!(status, 200);
+        assert_eq!(response["words"][0]["word"], "CAT");
+
+        let (status, response) = post("/solve?verbose", r#"{"puzzle": ["CAT"]}"#);
+        assert_eq!(status, 200);
+        assert_eq!(response["score"], 2021);
+
+        let (status, response) = post(
+            "/verify",
+            r#"{"puzzle": ["CAT"], "solution": [[[0, 0, "C"], [0, 1, "A"], [0, 2, "T"]]]}"#,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(response["valid"], true);
+        assert_eq!(response["score"], 2021);
+    }
+
+    #[test]
+    fn solves_empty_boards() {
+        let (status, response) = post("/solve", r#"{"puzzle": ["   "]}"#);
+        assert_eq!(status, 200);
+        assert_eq!(response["score"], 2000);
+        assert_eq!(response["solution"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn turns_away_bad_requests() {
+        let too_big = "x".repeat(1001);
+        let status = |method, url, body| answer(method, url, body).0;
+        assert_eq!(status(tiny_http::Method::Get, "/solve", ""), 405);
+        assert_eq!(status(tiny_http::Method::Post, "/nowhere", ""), 404);
+        assert_eq!(status(tiny_http::Method::Post, "/words", &too_big), 413);
+        assert_eq!(status(tiny_http::Method::Post, "/words", "{"), 400);
+        assert_eq!(
+            status(
+                tiny_http::Method::Post,
+                "/words",
+                r#"{"puzzle": ["DOG", "CATS"]}"#
+            ),
+            400
+        );
+        assert_eq!(
+            status(tiny_http::Method::Post, "/verify", r#"{"puzzle": ["CAT"]}"#),
+            400
+        );
+    }
+
+    #[test]
+    fn reports_illegal_moves() {
+        let (status, response) = post(
+            "/verify",
+            r#"{"puzzle": ["CAT"], "solution": [[[0, 2, "T"], [0, 1, "A"], [0, 0, "C"]]]}"#,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(response["valid"], false);
+        assert_eq!(response["move"], 1);
+    }
+}
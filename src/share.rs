@@ -0,0 +1,96 @@
+//! A spoiler-free summary of a solve, for sharing the way Puzzmo players do.
+
+use crate::{Rules, Tile, Tower};
+
+/// The colour for the tiles each move cleared, for solutions with no more moves than colours.
+const MOVES: &[&str] = &["🟥", "🟧", "🟨", "🟩", "🟦", "🟪", "🟫"];
+/// Tiles that were never cleared.
+const LEFT: &str = "⬛";
+/// Cells that started out empty.
+const EMPTY: &str = "⬜";
+/// What longer solutions show instead of [`LEFT`] and [`EMPTY`], with each cleared tile numbered
+/// by its move.
+const LEFT_NUMBERED: &str = "#";
+const EMPTY_NUMBERED: &str = ".";
+
+/// Which move cleared each tile of `tower`, by where the tile started.
+pub fn cleared_by(tower: &Tower, solution: &crate::Solution) -> ndarray::Array2<Option<usize>> {
+    let mut cleared_by = ndarray::Array2::from_elem(tower.dim(), None);
+
+    // Where each tile on the tower as it is now started out.
+    let mut origin = ndarray::Array2::from_shape_fn(tower.dim(), Some);
+    let mut tower = tower.clone();
+
+    for (k, path) in solution.iter().enumerate() {
+        let diff = crate::delete_path(&mut tower, path);
+        for cleared in diff.cleared.iter() {
+            if let Some(cell) = origin[cleared.cell].take() {
+                cleared_by[cell] = Some(k);
+            }
+        }
+        for fall in diff.fallen.iter() {
+            origin[fall.to] = origin[fall.from].take();
+        }
+    }
+
+    cleared_by
+}
+
+/// Writes the day, score, number of words and longest word of a solution, whether it cleared the
+/// tower or else got it almost there, and a grid of the starting tower coloured by the move that
+/// cleared each tile, or numbered by it when there are more moves than colours.
+pub fn card(title: &str, tower: &Tower, solution: &crate::Solution, rules: &Rules) -> String {
+    let mut end = tower.clone();
+    for path in solution.iter() {
+        crate::delete_path(&mut end, path);
+    }
+
+    let mut lines = vec![
+        title.to_string(),
+        format!(
+            "🏆 {} · 📝 {} {} · 📏 longest {}",
+            crate::score_solution(tower, solution, rules),
+            solution.len(),
+            if solution.len() == 1 { "word" } else { "words" },
+            solution.iter().map(|path| path.len()).max().unwrap_or(0)
+        ),
+    ];
+
+    // A cleared tower is always almost there too, so only the better of the two is worth saying.
+    let bonuses = crate::end_bonuses(&end);
+    if bonuses.contains(&crate::EndBonus::Cleared) {
+        lines.push("🧹 Tower cleared!".to_string());
+    } else if bonuses.contains(&crate::EndBonus::AlmostThere) {
+        lines.push("🤏 Almost there!".to_string());
+    }
+
+    let numbered = solution.len() > MOVES.len();
+    let width = solution.len().to_string().len();
+    let cleared_by = cleared_by(tower, solution);
+    lines.extend(
+        cleared_by
+            .rows()
+            .into_iter()
+            .zip(tower.rows())
+            .map(|(row, tiles)| {
+                let cells = row
+                    .iter()
+                    .zip(tiles.iter())
+                    .map(|(k, &tile)| match (k, numbered) {
+                        (Some(k), false) => MOVES[*k].to_string(),
+                        (Some(k), true) => format!("{:>width$}", k + 1),
+                        (None, false) if tile == Tile::Empty => EMPTY.to_string(),
+                        (None, false) => LEFT.to_string(),
+                        (None, true) if tile == Tile::Empty => format!("{EMPTY_NUMBERED:>width$}"),
+                        (None, true) => format!("{LEFT_NUMBERED:>width$}"),
+                    });
+                if numbered {
+                    cells.collect::<Vec<_>>().join(" ")
+                } else {
+                    cells.collect::<String>()
+                }
+            }),
+    );
+
+    lines.join("\n")
+}
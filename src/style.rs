@@ -0,0 +1,105 @@
+//! How [`crate::pretty_tower`] draws towers in the terminal.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Charset {
+    /// Box-drawing characters.
+    #[default]
+    Unicode,
+    /// Plain ASCII, for terminals and fonts without box-drawing characters.
+    Ascii,
+}
+
+/// The ANSI SGR parameters used for each kind of highlighted cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Theme {
+    /// The first tile of the word.
+    pub first: &'static str,
+    /// The other tiles of the word.
+    pub path: &'static str,
+    /// Tiles the word would clear.
+    pub deletable: &'static str,
+    /// The lines joining the tiles of the word.
+    pub link: &'static str,
+}
+
+impl Theme {
+    pub const MAGENTA: Theme = Theme {
+        first: "1;37;45",
+        path: "37;45",
+        deletable: "35",
+        link: "1;35",
+    };
+
+    pub const BLUE: Theme = Theme {
+        first: "1;37;44",
+        path: "37;44",
+        deletable: "34",
+        link: "1;34",
+    };
+
+    pub const GREEN: Theme = Theme {
+        first: "1;30;42",
+        path: "30;42",
+        deletable: "32",
+        link: "1;32",
+    };
+
+    /// Only bold, underline and reverse video, which work whatever the terminal's palette is.
+    pub const MONOCHROME: Theme = Theme {
+        first: "1;7",
+        path: "7",
+        deletable: "4",
+        link: "1",
+    };
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::MAGENTA
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Style {
+    pub charset: Charset,
+    /// The colours to use, or `None` for no escape sequences at all, in which case the word's
+    /// tiles are marked `[A]` and the tiles it would clear `*A*`.
+    pub theme: Option<Theme>,
+    /// Whether to label the rows and columns of the tower.
+    pub labels: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            charset: Charset::default(),
+            theme: Some(Theme::default()),
+            labels: false,
+        }
+    }
+}
+
+impl Style {
+    /// Plain text, for writing to files and other programs.
+    pub const PLAIN: Style = Style {
+        charset: Charset::Unicode,
+        theme: None,
+        labels: false,
+    };
+
+    /// Wraps `s` in the escape sequence `sgr` picks from the theme, or leaves it alone without one.
+    pub fn paint(&self, s: &str, sgr: impl Fn(&Theme) -> &'static str) -> String {
+        match &self.theme {
+            Some(theme) => format!("\x1b[{}m{s}\x1b[0m", sgr(theme)),
+            None => s.to_string(),
+        }
+    }
+
+    /// Picks `unicode` or `ascii`, whichever the charset calls for.
+    pub fn pick(&self, unicode: &'static str, ascii: &'static str) -> &'static str {
+        match self.charset {
+            Charset::Unicode => unicode,
+            Charset::Ascii => ascii,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+/// A single cell of a tower.
+///
+/// New kinds of tiles get their own variant here; anything the solver does not understand yet is
+/// kept around as [`Tile::Other`] so that conversions to and from the Puzzmo text format stay
+/// lossless.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, serde::Serialize)]
+#[serde(into = "char")]
+pub enum Tile {
+    /// Nothing is here, e.g. because the tile fell or was cleared.
+    #[default]
+    Empty,
+
+    /// A blank tile, which cannot be used in words but is cleared by adjacent words.
+    Blank,
+
+    /// A tile that can stand for any letter.
+    Wildcard,
+
+    /// A letter tile. `letter` is always upper-case, and `bonus` tiles multiply the score of the
+    /// word they are used in.
+    Letter { letter: char, bonus: bool },
+
+    /// A tile we don't know about, stored verbatim.
+    Other(char),
+}
+
+impl Tile {
+    /// Whether nothing is here.
+    pub fn is_empty(self) -> bool {
+        self == Tile::Empty
+    }
+
+    /// Whether this is a bonus letter tile.
+    pub fn is_bonus(self) -> bool {
+        matches!(self, Tile::Letter { bonus: true, .. })
+    }
+
+    /// Whether this tile can be played as the upper-case `letter`.
+    pub fn can_be(self, letter: char) -> bool {
+        match self {
+            Tile::Letter { letter: l, .. } => l == letter,
+            Tile::Wildcard => letter.is_ascii_uppercase(),
+            _ => false,
+        }
+    }
+
+    /// The letter on this tile, if it is a letter tile.
+    pub fn letter(self) -> Option<char> {
+        match self {
+            Tile::Letter { letter, .. } => Some(letter),
+            _ => None,
+        }
+    }
+}
+
+impl From<char> for Tile {
+    fn from(c: char) -> Self {
+        match c {
+            ' ' => Tile::Empty,
+            '_' => Tile::Blank,
+            '?' => Tile::Wildcard,
+            'A'..='Z' => Tile::Letter {
+                letter: c,
+                bonus: false,
+            },
+            'a'..='z' => Tile::Letter {
+                letter: c.to_ascii_uppercase(),
+                bonus: true,
+            },
+            c => Tile::Other(c),
+        }
+    }
+}
+
+impl From<Tile> for char {
+    fn from(tile: Tile) -> Self {
+        match tile {
+            Tile::Empty => ' ',
+            Tile::Blank => '_',
+            Tile::Wildcard => '?',
+            Tile::Letter {
+                letter,
+                bonus: false,
+            } => letter,
+            Tile::Letter {
+                letter,
+                bonus: true,
+            } => letter.to_ascii_lowercase(),
+            Tile::Other(c) => c,
+        }
+    }
+}
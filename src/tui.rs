@@ -0,0 +1,493 @@
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind},
+    execute, queue, style, terminal,
+};
+use spelltower::{game, hint, words, Rules, Tile, Tower};
+
+const ORIGIN: (u16, u16) = (2, 2);
+const FRAME: std::time::Duration = std::time::Duration::from_millis(40);
+
+struct App<'a> {
+    game: game::Game<'a>,
+    root: &'a words::Node,
+    style: spelltower::Style,
+    cursor: (usize, usize),
+    selection: Vec<(usize, usize)>,
+    message: String,
+}
+
+/// Keeps the terminal in raw mode on the alternate screen until dropped, so that it is put back
+/// however we leave, including by panicking.
+pub struct Screen {
+    mouse: bool,
+}
+
+impl Screen {
+    pub fn enter(mouse: bool) -> anyhow::Result<Screen> {
+        // Restore the terminal before the panic message is printed, or it is lost with the
+        // alternate screen.
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore(mouse);
+            hook(info);
+        }));
+
+        let screen = Screen { mouse };
+        terminal::enable_raw_mode()?;
+        execute!(
+            std::io::stdout(),
+            terminal::EnterAlternateScreen,
+            cursor::Hide
+        )?;
+        if mouse {
+            execute!(std::io::stdout(), event::EnableMouseCapture)?;
+        }
+        Ok(screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        restore(self.mouse);
+        if !std::thread::panicking() {
+            // Drops our hook, going back to the default one.
+            drop(std::panic::take_hook());
+        }
+    }
+}
+
+fn restore(mouse: bool) {
+    let mut stdout = std::io::stdout();
+    if mouse {
+        let _ = execute!(stdout, event::DisableMouseCapture);
+    }
+    let _ = execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+}
+
+pub fn run(
+    tower: Tower,
+    root: &words::Node,
+    rules: Rules,
+    style: spelltower::Style,
+) -> anyhow::Result<()> {
+    let _screen = Screen::enter(true)?;
+
+    App {
+        game: game::Game::new(tower, root, rules),
+        root,
+        style,
+        cursor: (0, 0),
+        selection: vec![],
+        message: String::new(),
+    }
+    .run(&mut std::io::stdout())
+}
+
+pub fn screen_position((i, j): (usize, usize)) -> (u16, u16) {
+    (ORIGIN.0 + j as u16 * 4, ORIGIN.1 + i as u16 * 2)
+}
+
+/// How a tile on screen is highlighted.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    None,
+    /// The first tile of the word.
+    First,
+    /// The other tiles of the word.
+    Path,
+    /// Tiles the word would clear.
+    Deletable,
+}
+
+/// Draws the tile `c` in `style`'s theme, or without one marked the way
+/// [`spelltower::pretty_tower`] marks it, and under the cursor in reverse video, or as `>A<`
+/// without a theme.
+pub fn paint_tile(
+    c: impl std::fmt::Display,
+    highlight: Highlight,
+    cursor: bool,
+    style: &spelltower::Style,
+) -> String {
+    let content = format!(" {c} ");
+    let tile = match (style.theme, highlight) {
+        (_, Highlight::None) => content,
+        (Some(_), Highlight::First) => style.paint(&content, |t| t.first),
+        (Some(_), Highlight::Path) => style.paint(&content, |t| t.path),
+        (Some(_), Highlight::Deletable) => style.paint(&content, |t| t.deletable),
+        (None, Highlight::First | Highlight::Path) => format!("[{c}]"),
+        (None, Highlight::Deletable) => format!("*{c}*"),
+    };
+    match (cursor, style.theme) {
+        (false, _) => tile,
+        (true, Some(_)) => format!("\x1b[7m{tile}\x1b[0m"),
+        (true, None) => format!(">{c}<"),
+    }
+}
+
+/// What `tile` is drawn as, with blanks shaded in `style`'s charset.
+pub fn tile_char(tile: Tile, style: &spelltower::Style) -> String {
+    match tile {
+        Tile::Blank => style.pick("░", "#").to_string(),
+        tile => char::from(tile).to_string(),
+    }
+}
+
+/// Draws the title above the tower, in bold if `style` has a theme.
+pub fn title(style: &spelltower::Style) -> String {
+    style.paint("SPELLTOWER", |_| "1")
+}
+
+/// Draws the lines joining the tiles of a word.
+pub fn draw_links(
+    out: &mut impl std::io::Write,
+    cells: &[(usize, usize)],
+    style: &spelltower::Style,
+) -> anyhow::Result<()> {
+    for (&(ia, ja), &(ib, jb)) in cells.iter().zip(cells.iter().skip(1)) {
+        let (x, y) = screen_position((ia.min(ib), ja.min(jb)));
+        let (x, y, link) = match (ib as isize - ia as isize, jb as isize - ja as isize) {
+            (0, _) => (x + 3, y, style.pick("─", "-")),
+            (_, 0) => (x + 1, y + 1, style.pick("│", "|")),
+            (1, 1) | (-1, -1) => (x + 3, y + 1, style.pick("╲", "\\")),
+            _ => (x + 3, y + 1, style.pick("╱", "/")),
+        };
+        queue!(
+            out,
+            cursor::MoveTo(x, y),
+            style::Print(style.paint(link, |t| t.link))
+        )?;
+    }
+    Ok(())
+}
+
+impl<'a> App<'a> {
+    fn run(&mut self, out: &mut impl std::io::Write) -> anyhow::Result<()> {
+        loop {
+            self.draw(out)?;
+
+            match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    let (n, m) = self.game.tower().dim();
+                    match key.code {
+                        KeyCode::Char('q') => return Ok(()),
+                        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            return Ok(())
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            self.cursor.0 = self.cursor.0.saturating_sub(1)
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            self.cursor.0 = (self.cursor.0 + 1).min(n.saturating_sub(1))
+                        }
+                        KeyCode::Left | KeyCode::Char('h') => {
+                            self.cursor.1 = self.cursor.1.saturating_sub(1)
+                        }
+                        KeyCode::Right | KeyCode::Char('l') => {
+                            self.cursor.1 = (self.cursor.1 + 1).min(m.saturating_sub(1))
+                        }
+                        KeyCode::Char(' ') => self.select(self.cursor),
+                        KeyCode::Backspace => {
+                            self.selection.pop();
+                        }
+                        KeyCode::Esc => self.selection.clear(),
+                        KeyCode::Enter => self.play(out)?,
+                        KeyCode::Char('u') => {
+                            self.selection.clear();
+                            self.message = match self.game.undo() {
+                                Some(_) => "undone".to_string(),
+                                None => "nothing to undo".to_string(),
+                            };
+                        }
+                        KeyCode::Char('r') => {
+                            self.selection.clear();
+                            self.message = match self.game.redo() {
+                                Some(_) => "redone".to_string(),
+                                None => "nothing to redo".to_string(),
+                            };
+                        }
+                        KeyCode::Char('?') => self.hint(),
+                        _ => {}
+                    }
+                }
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                    let (n, m) = self.game.tower().dim();
+                    let (x, y) = (
+                        mouse.column.checked_sub(ORIGIN.0),
+                        mouse.row.checked_sub(ORIGIN.1),
+                    );
+                    if let (Some(x), Some(y)) = (x, y) {
+                        let (i, j) = ((y / 2) as usize, (x / 4) as usize);
+                        if y % 2 == 0 && x % 4 != 3 && i < n && j < m {
+                            self.cursor = (i, j);
+                            self.select((i, j));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Adds `cell` to the word being built, or takes it and everything after it back off.
+    fn select(&mut self, cell: (usize, usize)) {
+        if let Some(k) = self.selection.iter().position(|&c| c == cell) {
+            self.selection.truncate(k);
+            return;
+        }
+
+        // The cursor can be outside a tower with no rows or columns.
+        if !matches!(
+            self.game.tower().get(cell),
+            Some(Tile::Letter { .. } | Tile::Wildcard)
+        ) {
+            return;
+        }
+
+        let adjacent = self
+            .selection
+            .last()
+            .is_some_and(|&(i, j)| i.abs_diff(cell.0) <= 1 && j.abs_diff(cell.1) <= 1);
+        if !adjacent {
+            self.selection.clear();
+        }
+        self.selection.push(cell);
+    }
+
+    fn play(&mut self, out: &mut impl std::io::Write) -> anyhow::Result<()> {
+        let tower = self.game.tower().clone();
+        let Some(path) = game::read_word(&tower, self.root, self.game.rules(), &self.selection)
+        else {
+            self.message = "not a word".to_string();
+            return Ok(());
+        };
+
+        let m = self.game.play(&path)?.clone();
+        self.selection.clear();
+        self.message = format!(
+            "played {} for {}",
+            m.path.iter().map(|&(_, _, c)| c).collect::<String>(),
+            m.score
+        );
+
+        self.animate(out, &tower, &m.diff)
+    }
+
+    fn hint(&mut self) {
+        let Some(hint) = hint::hints(self.game.tower(), self.root, self.game.rules(), 1, 20)
+            .into_iter()
+            .next()
+        else {
+            self.message = "no moves left".to_string();
+            return;
+        };
+
+        self.selection = hint.path.iter().map(|&(i, j, _)| (i, j)).collect();
+        self.message = format!(
+            "hint: {} for {} ({} with a greedy continuation)",
+            hint.path.iter().map(|&(_, _, c)| c).collect::<String>(),
+            hint.score,
+            hint.lookahead
+        );
+    }
+
+    /// Flashes the cleared tiles, then drops the surviving tiles one row per frame.
+    fn animate(
+        &self,
+        out: &mut impl std::io::Write,
+        before: &Tower,
+        diff: &spelltower::Diff,
+    ) -> anyhow::Result<()> {
+        let cleared = diff.cleared.iter().map(|c| c.cell).collect::<Vec<_>>();
+        for flash in 0..4 {
+            self.draw_tower(out, before, if flash % 2 == 0 { &cleared } else { &[] })?;
+            out.flush()?;
+            std::thread::sleep(FRAME * 2);
+        }
+
+        let mut tower = before.clone();
+        for &cell in cleared.iter() {
+            tower[cell] = Tile::Empty;
+        }
+        for fall in diff.fallen.iter() {
+            tower[fall.from] = Tile::Empty;
+        }
+
+        let steps = diff
+            .fallen
+            .iter()
+            .map(|fall| fall.to.0 - fall.from.0)
+            .max()
+            .unwrap_or(0);
+        for step in 0..=steps {
+            let mut frame = tower.clone();
+            for fall in diff.fallen.iter() {
+                frame[((fall.from.0 + step).min(fall.to.0), fall.from.1)] = fall.tile;
+            }
+            self.draw_tower(out, &frame, &[])?;
+            out.flush()?;
+            std::thread::sleep(FRAME);
+        }
+
+        while event::poll(std::time::Duration::ZERO)? {
+            event::read()?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_tower(
+        &self,
+        out: &mut impl std::io::Write,
+        tower: &Tower,
+        flashing: &[(usize, usize)],
+    ) -> anyhow::Result<()> {
+        for ((i, j), &tile) in tower.indexed_iter() {
+            let (x, y) = screen_position((i, j));
+            let c = tile_char(tile, &self.style);
+            let highlight = if flashing.contains(&(i, j)) {
+                Highlight::First
+            } else {
+                Highlight::None
+            };
+            queue!(
+                out,
+                cursor::MoveTo(x, y),
+                style::Print(paint_tile(c, highlight, false, &self.style))
+            )?;
+        }
+        Ok(())
+    }
+
+    fn draw(&self, out: &mut impl std::io::Write) -> anyhow::Result<()> {
+        let tower = self.game.tower();
+        let rules = self.game.rules();
+        let (n, _) = tower.dim();
+
+        let readings = game::readings(tower, self.root, &self.selection);
+        let word = game::read_word(tower, self.root, rules, &self.selection);
+        let deletable = word
+            .as_ref()
+            .map(|path| spelltower::deletable(tower, path))
+            .unwrap_or_default();
+
+        queue!(
+            out,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(ORIGIN.0, 0),
+            style::Print(title(&self.style))
+        )?;
+
+        for ((i, j), &tile) in tower.indexed_iter() {
+            let (x, y) = screen_position((i, j));
+            let c = tile_char(tile, &self.style);
+            let highlight = if self.selection.first() == Some(&(i, j)) {
+                Highlight::First
+            } else if self.selection.contains(&(i, j)) {
+                Highlight::Path
+            } else if deletable.contains(&(i, j)) {
+                Highlight::Deletable
+            } else {
+                Highlight::None
+            };
+            queue!(
+                out,
+                cursor::MoveTo(x, y),
+                style::Print(paint_tile(c, highlight, self.cursor == (i, j), &self.style))
+            )?;
+        }
+
+        draw_links(out, &self.selection, &self.style)?;
+
+        let status = match (&word, readings.is_empty()) {
+            (Some(path), _) => format!(
+                "{} is a word, worth {}",
+                path.iter().map(|&(_, _, c)| c).collect::<String>(),
+                spelltower::score_path(tower, path, rules)
+            ),
+            (None, _) if self.selection.is_empty() => String::new(),
+            (None, false) => format!(
+                "{}...",
+                readings[0].0.iter().map(|&(_, _, c)| c).collect::<String>()
+            ),
+            (None, true) => "no word starts like this".to_string(),
+        };
+
+        let bottom = ORIGIN.1 + n as u16 * 2;
+        for (k, line) in [
+            status,
+            format!(
+                "score: {}   moves: {}",
+                self.game.total_score(),
+                self.game.history().len()
+            ),
+            self.message.clone(),
+            "arrows/hjkl move  space/click select  enter play  backspace/esc unselect  u undo  r redo  ? hint  q quit".to_string(),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            queue!(
+                out,
+                cursor::MoveTo(ORIGIN.0, bottom + k as u16),
+                style::Print(line)
+            )?;
+        }
+
+        out.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that `frame` is ASCII, and only moves the cursor and clears the screen, without
+    /// setting any colours or attributes.
+    fn assert_plain(frame: &[u8]) {
+        let frame = std::str::from_utf8(frame).unwrap();
+        assert!(frame.is_ascii(), "{frame:?}");
+        for sequence in frame.split('\x1b').skip(1) {
+            let command =
+                sequence.trim_start_matches(|c: char| c == '[' || c == ';' || c.is_ascii_digit());
+            assert!(!command.starts_with('m'), "{frame:?}");
+        }
+    }
+
+    #[test]
+    fn draws_plain_ascii_frames() {
+        let (root, _) = words::load();
+        let tower = spelltower::puzzle::parse("SpellTower\n3x2\nCAT\n_O?\n")
+            .unwrap()
+            .tower;
+        let style = spelltower::Style {
+            charset: spelltower::style::Charset::Ascii,
+            ..spelltower::Style::PLAIN
+        };
+        let app = App {
+            game: game::Game::new(tower.clone(), root, Rules::default()),
+            root,
+            style,
+            cursor: (1, 0),
+            // Joined across, diagonally and up.
+            selection: vec![(0, 0), (0, 1), (1, 2), (0, 2)],
+            message: String::new(),
+        };
+
+        let mut frame = vec![];
+        app.draw(&mut frame).unwrap();
+        assert_plain(&frame);
+        let frame = String::from_utf8(frame).unwrap();
+        assert!(frame.contains(">#<"));
+        assert!(frame.contains("[C]"));
+        for link in ["-", "\\", "|"] {
+            assert!(frame.contains(link), "{link}");
+        }
+
+        let mut frame = vec![];
+        app.draw_tower(&mut frame, &tower, &[(0, 0)]).unwrap();
+        assert_plain(&frame);
+    }
+}
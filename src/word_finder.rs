@@ -0,0 +1,75 @@
+//! Every distinct word that can be played on a tower.
+
+use crate::{ClearReason, Path, Rules, Tower};
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Word {
+    pub word: String,
+    /// The highest-scoring path spelling the word.
+    pub path: Path,
+    pub score: usize,
+    /// How many tiles playing the word would clear, including the word itself.
+    pub cleared: usize,
+    /// Whether the word clears a row by using a J, Q, X or Z.
+    pub rare_letter_clear: bool,
+    /// Whether the word is 5 or more letters long and clears the letters next to it, which shorter
+    /// words leave standing. Blanks next to a word are cleared whatever its length, so they don't
+    /// count.
+    pub long_word_clear: bool,
+}
+
+/// Finds every word on `tower`, each with its best path, in no particular order.
+pub fn find_words(tower: &Tower, root: &crate::words::Node, rules: &Rules) -> Vec<Word> {
+    let mut best = std::collections::HashMap::<String, Word>::new();
+
+    for path in crate::find_paths(tower, root) {
+        let score = crate::score_path(tower, &path, rules);
+        let word = path
+            .iter()
+            .map(|&(_, _, letter)| letter)
+            .collect::<String>();
+
+        if best.get(&word).is_some_and(|w| w.score >= score) {
+            continue;
+        }
+
+        let reasons = crate::clear_reasons(tower, &path);
+        // A letter in the row of a rare letter is recorded as cleared by that, even if it is also
+        // next to the word.
+        let next_to_path = |&(i, j): &(usize, usize)| {
+            path.iter()
+                .any(|&(pi, pj, _)| pi.abs_diff(i) + pj.abs_diff(j) == 1)
+        };
+        best.insert(
+            word.clone(),
+            Word {
+                word,
+                score,
+                cleared: reasons.len(),
+                rare_letter_clear: reasons.values().any(|&r| r == ClearReason::RareLetterRow),
+                long_word_clear: path.len() >= 5
+                    && reasons.iter().any(|(cell, &reason)| {
+                        matches!(reason, ClearReason::Adjacent | ClearReason::RareLetterRow)
+                            && next_to_path(cell)
+                    }),
+                path,
+            },
+        );
+    }
+
+    best.into_values().collect()
+}
+
+/// Whether `word` has every letter of `letters`, as many times as `letters` has it.
+pub fn contains_letters(word: &str, letters: &str) -> bool {
+    let mut word = word.chars().collect::<Vec<_>>();
+    letters
+        .chars()
+        .all(|c| match word.iter().position(|&w| w == c) {
+            Some(k) => {
+                word.swap_remove(k);
+                true
+            }
+            None => false,
+        })
+}
@@ -1,43 +1,82 @@
-use std::io::BufRead as _;
+//! The dictionary, compiled by `build.rs` into a minimised DAWG and walked where it lies in the
+//! binary, so there is nothing to parse or allocate at startup.
 
-#[derive(Default, Debug)]
-pub struct Node {
-    children: [Option<Box<Node>>; 26],
-    is_end: bool,
-}
+static DAWG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/dictionary.dawg"));
+
+const END: u32 = 1 << 31;
+
+/// A node of the dictionary: the bytes of the DAWG from this node to the end.
+#[repr(transparent)]
+pub struct Node([u8]);
 
 impl Node {
+    fn new(bytes: &[u8]) -> &Node {
+        // SAFETY: `Node` is a `repr(transparent)` wrapper around `[u8]`.
+        unsafe { &*(bytes as *const [u8] as *const Node) }
+    }
+
+    fn read(&self, k: usize) -> u32 {
+        u32::from_le_bytes(self.0[k * 4..k * 4 + 4].try_into().unwrap())
+    }
+
+    fn header(&self) -> u32 {
+        self.read(0)
+    }
+
+    /// The child at edge `k` of this node.
+    fn child(&self, k: usize) -> &Node {
+        Node::new(&self.0[self.read(k + 1) as usize..])
+    }
+
+    /// The child for the upper-case letter `c`, if a word continues with it.
     pub fn get(&self, c: char) -> Option<&Node> {
-        if !('A'..='Z').contains(&c) {
+        if !c.is_ascii_uppercase() {
             return None;
         }
-        self.children
-            .get(c as usize - 'A' as usize)
-            .map(|v| v.as_ref().map(|v| v.as_ref()))
-            .flatten()
+        let bit = 1 << (c as u32 - 'A' as u32);
+        let header = self.header();
+        if header & bit == 0 {
+            return None;
+        }
+        Some(self.child((header & (bit - 1)).count_ones() as usize))
     }
 
+    /// Every child of this node, in alphabetical order.
+    pub fn children(&self) -> impl Iterator<Item = (char, &Node)> {
+        let header = self.header();
+        ('A'..='Z')
+            .filter(move |&c| header & 1 << (c as u32 - 'A' as u32) != 0)
+            .enumerate()
+            .map(|(k, c)| (c, self.child(k)))
+    }
+
+    /// Whether the letters leading to this node spell a word.
     pub fn is_end(&self) -> bool {
-        self.is_end
+        self.header() & END != 0
     }
-}
 
-pub fn load() -> (Node, usize) {
-    let mut max_len = 0;
-    let mut root = Node::default();
+    /// Whether the upper-case `word` is in the dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        word.chars()
+            .try_fold(self, |node, c| node.get(c))
+            .is_some_and(|node| node.is_end())
+    }
+}
 
-    for word in std::io::BufReader::new(std::io::Cursor::new(include_str!("dictionary"))).lines() {
-        let word = word.unwrap();
-        let mut node = &mut root;
-        for letter in word.chars() {
-            node = node.children[letter as usize - 'A' as usize]
-                .get_or_insert_with(|| Box::new(Node::default()));
-        }
-        if word.len() > max_len {
-            max_len = word.len();
-        }
-        node.is_end = true;
+impl std::fmt::Debug for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Node")
+            .field(
+                "children",
+                &self.children().map(|(c, _)| c).collect::<String>(),
+            )
+            .field("is_end", &self.is_end())
+            .finish()
     }
+}
 
-    (root, max_len)
+/// The root of the dictionary, and the length of its longest word.
+pub fn load() -> (&'static Node, usize) {
+    let max_len = u32::from_le_bytes(DAWG[..4].try_into().unwrap()) as usize;
+    (Node::new(&DAWG[4..]), max_len)
 }
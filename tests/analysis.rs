@@ -0,0 +1,55 @@
+mod common;
+
+use common::tower;
+use spelltower::analysis::{self, MoveAnalysis};
+use spelltower::hint::Hint;
+use spelltower::{words, Rules};
+
+fn analysis(regret: usize) -> MoveAnalysis {
+    let hint = Hint {
+        path: vec![],
+        score: 0,
+        lookahead: regret,
+    };
+    MoveAnalysis {
+        path: vec![],
+        score: 0,
+        value: 0,
+        best: hint,
+        regret,
+    }
+}
+
+#[test]
+fn compares_each_move_with_the_best() {
+    let (root, _) = words::load();
+    let rules = Rules::default();
+    let tower = tower("SDOGS\nCATER");
+    let solution = spelltower::solve_greedy(&tower, root, &rules);
+
+    let moves = analysis::analyze(&tower, root, &rules, &solution, 10);
+    assert_eq!(moves.len(), solution.len());
+
+    let mut position = tower.clone();
+    for (m, path) in moves.iter().zip(solution.iter()) {
+        assert_eq!(&m.path, path);
+        assert_eq!(m.score, spelltower::score_path(&position, path, &rules));
+        assert!(m.value >= m.score);
+        assert_eq!(m.regret, m.best.lookahead - m.value);
+        spelltower::delete_path(&mut position, path);
+    }
+}
+
+#[test]
+fn finds_the_biggest_mistake() {
+    assert_eq!(analysis::biggest_mistake(&[]), None);
+    assert_eq!(analysis::biggest_mistake(&[analysis(0), analysis(0)]), None);
+    assert_eq!(
+        analysis::biggest_mistake(&[analysis(0), analysis(5), analysis(3)]),
+        Some(1)
+    );
+    assert_eq!(
+        analysis::biggest_mistake(&[analysis(5), analysis(5)]),
+        Some(1)
+    );
+}
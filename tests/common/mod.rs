@@ -0,0 +1,10 @@
+//! Fixtures shared by the integration tests.
+
+/// A tower from its rows, e.g. `"DOG\nCAT"`, with `_` for blanks and `?` for wildcards.
+pub fn tower(rows: &str) -> spelltower::Tower {
+    let width = rows.lines().next().unwrap().len();
+    let height = rows.lines().count();
+    spelltower::puzzle::parse(&format!("SpellTower\n{width}x{height}\n{rows}"))
+        .unwrap()
+        .tower
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::tower;
+use spelltower::{compare, Rules, Style};
+
+#[test]
+fn compares_two_move_solutions() {
+    let rules = Rules::default();
+    let dog_cat = tower("DOG\nCAT");
+    let cat = spelltower::find_word_paths(&dog_cat, "CAT").remove(0);
+    let mut after = dog_cat.clone();
+    spelltower::delete_path(&mut after, &cat);
+    let dog = spelltower::find_word_paths(&after, "DOG").remove(0);
+    let god = spelltower::find_word_paths(&after, "GOD").remove(0);
+
+    let a = vec![cat.clone(), dog];
+    let b = vec![cat, god];
+    let out = compare::compare(
+        &dog_cat,
+        &a,
+        &b,
+        ["a.json", "b.json"],
+        &rules,
+        &Style::PLAIN,
+    );
+    assert!(out.contains("1 moves in common\nfirst difference at move 2:\n"));
+    let first_difference = out.lines().find(|line| line.contains("DOG (21)")).unwrap();
+    assert!(first_difference.contains("GOD (21)"));
+    let table = out.lines().skip_while(|line| !line.is_empty());
+    assert_eq!(
+        table.collect::<Vec<_>>(),
+        [
+            "",
+            "move  a.json           total  b.json           total",
+            "   1  CAT                 21  CAT                 21",
+            "   2* DOG                 42  GOD                 42",
+            "      TOTAL SCORE       2042  TOTAL SCORE       2042",
+        ]
+    );
+}
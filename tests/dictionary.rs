@@ -0,0 +1,70 @@
+//! Checks that the compiled dictionary holds exactly the words in the word list, and that lookups
+//! in it find them.
+
+use spelltower::{lookup, words};
+
+fn count(node: &words::Node) -> usize {
+    usize::from(node.is_end())
+        + node
+            .children()
+            .map(|(_, child)| count(child))
+            .sum::<usize>()
+}
+
+#[test]
+fn contains_every_word() {
+    let (root, max_len) = words::load();
+    let list = include_str!("../src/dictionary");
+
+    for word in list.lines() {
+        assert!(root.contains(word), "{word} is missing");
+    }
+    assert_eq!(max_len, list.lines().map(str::len).max().unwrap());
+}
+
+#[test]
+fn contains_nothing_else() {
+    let (root, _) = words::load();
+    let list = include_str!("../src/dictionary");
+    let words = list.lines().collect::<std::collections::HashSet<_>>();
+
+    assert_eq!(count(root), words.len());
+    assert!(!root.is_end());
+    assert!(!root.contains("QWXZ"));
+    assert!(root.get('a').is_none());
+}
+
+#[test]
+fn lookups_agree_with_the_word_list() {
+    let (root, _) = words::load();
+    let list = include_str!("../src/dictionary");
+    let mut sorted = list.lines().map(str::to_string).collect::<Vec<_>>();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    assert_eq!(lookup::with_prefix(root, ""), sorted);
+    assert_eq!(lookup::matching(root, "*").unwrap(), sorted);
+    assert_eq!(
+        lookup::with_prefix(root, "quiz"),
+        lookup::matching(root, "QUIZ**").unwrap()
+    );
+    assert!(lookup::matching(root, "?A?")
+        .unwrap()
+        .iter()
+        .all(|word| word.len() == 3 && word.as_bytes()[1] == b'A'));
+    assert_eq!(
+        lookup::matching(root, "q1"),
+        Err(lookup::Error::InvalidPattern('1'))
+    );
+
+    let anagrams = lookup::anagrams(root, &lookup::Letters::parse("listen").unwrap());
+    assert!(anagrams.iter().any(|word| word == "SILENT"));
+    assert!(anagrams
+        .iter()
+        .all(|word| word.len() <= 6 && root.contains(word)));
+    assert!(
+        lookup::anagrams(root, &lookup::Letters::parse("q??z").unwrap())
+            .iter()
+            .any(|word| word == "QUIZ")
+    );
+}
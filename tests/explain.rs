@@ -0,0 +1,39 @@
+mod strategies;
+
+use proptest::prelude::*;
+use spelltower::{explain, Rules, WildcardScoring};
+use strategies::tower_and_path;
+
+fn rules() -> impl Strategy<Value = Rules> {
+    prop_oneof![Just(WildcardScoring::Zero), Just(WildcardScoring::Full)]
+        .prop_map(|wildcard_scoring| Rules { wildcard_scoring })
+}
+
+proptest! {
+    #[test]
+    fn breakdown_adds_up_to_the_score((tower, path) in tower_and_path(), rules in rules()) {
+        let breakdown = explain::explain(&tower, &path, &rules);
+
+        prop_assert_eq!(breakdown.score, spelltower::score_path(&tower, &path, &rules));
+        prop_assert_eq!(
+            breakdown.score,
+            breakdown.letter_sum * breakdown.length_multiplier * breakdown.bonus_multiplier
+        );
+
+        let cells = breakdown
+            .letters
+            .iter()
+            .chain(breakdown.adjacent.iter())
+            .chain(breakdown.rare_letter_rows.iter())
+            .chain(breakdown.blanks.iter())
+            .collect::<Vec<_>>();
+        prop_assert_eq!(
+            cells.iter().map(|cell| cell.points).sum::<usize>(),
+            breakdown.letter_sum
+        );
+        prop_assert_eq!(
+            cells.iter().map(|cell| cell.cell).collect::<std::collections::HashSet<_>>(),
+            spelltower::deletable(&tower, &path)
+        );
+    }
+}
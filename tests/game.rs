@@ -0,0 +1,112 @@
+mod common;
+
+use common::tower;
+use spelltower::game::{self, Error, Game};
+use spelltower::{words, EndBonus, Rules};
+
+const CAT: &[(usize, usize, char)] = &[(1, 0, 'C'), (1, 1, 'A'), (1, 2, 'T')];
+const DOG: &[(usize, usize, char)] = &[(1, 0, 'D'), (1, 1, 'O'), (1, 2, 'G')];
+
+#[test]
+fn plays_undoes_and_redoes() {
+    let (root, _) = words::load();
+    let start = tower("DOG\nCAT");
+    let mut game = Game::new(start.clone(), root, Rules::default());
+
+    assert!(!game.can_undo() && !game.can_redo());
+    assert_eq!(game.play(CAT).unwrap().score, (4 + 1 + 2) * 3);
+    let after_cat = game.tower().clone();
+    assert_eq!(after_cat, tower("   \nDOG"));
+
+    assert_eq!(game.undo().unwrap().path, CAT);
+    assert_eq!(game.tower(), &start);
+    assert!(game.can_redo());
+
+    assert_eq!(game.redo().unwrap().path, CAT);
+    assert_eq!(game.tower(), &after_cat);
+    assert!(!game.can_redo());
+
+    game.undo();
+    game.play(&[(0, 0, 'D'), (0, 1, 'O'), (0, 2, 'G')]).unwrap();
+    assert!(!game.can_redo(), "playing a new move discards the redo");
+    assert_eq!(game.history().len(), 1);
+}
+
+#[test]
+fn end_bonuses_only_count_once_the_game_is_over() {
+    let (root, _) = words::load();
+    let mut game = Game::new(tower("DOG\nCAT"), root, Rules::default());
+
+    // Every column is down to one tile, but DOG can still be played.
+    game.play(CAT).unwrap();
+    assert!(!game.is_over());
+    assert!(game.end_bonuses().is_empty());
+    assert_eq!(game.total_score(), game.score());
+
+    game.play(DOG).unwrap();
+    assert!(game.is_over());
+    assert_eq!(
+        game.end_bonuses(),
+        vec![EndBonus::AlmostThere, EndBonus::Cleared]
+    );
+    assert_eq!(game.total_score(), game.score() + 2000);
+    assert_eq!(
+        game.total_score(),
+        spelltower::score_solution(&tower("DOG\nCAT"), &game.solution(), &Rules::default())
+    );
+}
+
+#[test]
+fn keeps_legal_moves_up_to_date() {
+    let (root, _) = words::load();
+    let start = tower("DOG\nCAT");
+    let mut game = Game::new(start.clone(), root, Rules::default());
+    let legal = |tower| spelltower::find_paths(&tower, root);
+
+    assert_eq!(game.legal_moves(), legal(start.clone()));
+    game.play(CAT).unwrap();
+    assert_eq!(game.legal_moves(), legal(game.tower().clone()));
+    game.undo();
+    assert_eq!(game.legal_moves(), legal(start));
+    game.redo();
+    assert_eq!(game.legal_moves(), legal(game.tower().clone()));
+    game.play(DOG).unwrap();
+    assert!(game.legal_moves().is_empty());
+}
+
+#[test]
+fn rejects_illegal_paths() {
+    let (root, _) = words::load();
+    let tower = tower("DOG\nCAT");
+
+    for (path, error) in [
+        (vec![], Error::EmptyPath),
+        (
+            vec![(1, 0, 'C'), (1, 1, 'A'), (1, 3, 'T')],
+            Error::OutOfBounds(1, 3),
+        ),
+        (
+            vec![(1, 0, 'C'), (1, 1, 'A'), (1, 0, 'C')],
+            Error::Reused(1, 0),
+        ),
+        (
+            vec![(1, 0, 'C'), (1, 2, 'A'), (1, 1, 'T')],
+            Error::NotAdjacent(1, 2),
+        ),
+        (
+            vec![(1, 0, 'C'), (1, 1, 'O'), (1, 2, 'T')],
+            Error::WrongLetter(1, 1, 'O'),
+        ),
+        (
+            vec![(1, 2, 'T'), (1, 1, 'A'), (1, 0, 'C')],
+            Error::NotAWord("TAC".to_string()),
+        ),
+    ] {
+        assert_eq!(game::validate_path(&tower, root, &path), Err(error));
+    }
+
+    let mut game = Game::new(tower.clone(), root, Rules::default());
+    assert!(game.play(&[(1, 2, 'T'), (1, 1, 'A'), (1, 0, 'C')]).is_err());
+    assert_eq!(game.tower(), &tower);
+    assert!(!game.can_undo());
+}
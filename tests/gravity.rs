@@ -0,0 +1,84 @@
+mod reference;
+mod strategies;
+
+use proptest::prelude::*;
+use spelltower::Tile;
+use strategies::tower_and_path;
+
+proptest! {
+    #[test]
+    fn deletable_matches_reference((tower, path) in tower_and_path()) {
+        prop_assert_eq!(
+            spelltower::deletable(&tower, &path),
+            reference::deletable(&tower, &path)
+        );
+    }
+
+    #[test]
+    fn delete_path_matches_reference((tower, path) in tower_and_path()) {
+        let mut actual = tower.clone();
+        spelltower::delete_path(&mut actual, &path);
+
+        let mut expected = tower.clone();
+        reference::delete_path(&mut expected, &path);
+
+        prop_assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn no_gaps_below_a_tile((tower, path) in tower_and_path()) {
+        let mut tower = tower;
+        spelltower::delete_path(&mut tower, &path);
+
+        for column in tower.columns() {
+            let first = column.iter().position(|tile| !tile.is_empty());
+            if let Some(first) = first {
+                prop_assert!(column.iter().skip(first).all(|tile| !tile.is_empty()));
+            }
+        }
+    }
+
+    #[test]
+    fn columns_keep_their_surviving_tiles_in_order((tower, path) in tower_and_path()) {
+        let deleted = spelltower::deletable(&tower, &path);
+
+        let mut after = tower.clone();
+        spelltower::delete_path(&mut after, &path);
+
+        for (j, (before, after)) in tower.columns().into_iter().zip(after.columns()).enumerate() {
+            let expected = before
+                .iter()
+                .enumerate()
+                .filter(|&(i, tile)| !tile.is_empty() && !deleted.contains(&(i, j)))
+                .map(|(_, tile)| *tile)
+                .collect::<Vec<_>>();
+            let actual = after
+                .iter()
+                .filter(|tile| !tile.is_empty())
+                .cloned()
+                .collect::<Vec<_>>();
+            prop_assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn diff_replays_the_move((tower, path) in tower_and_path()) {
+        let mut after = tower.clone();
+        let diff = spelltower::delete_path(&mut after, &path);
+
+        let mut replayed = tower.clone();
+        for cleared in diff.cleared.iter() {
+            prop_assert_eq!(replayed[cleared.cell], cleared.tile);
+            replayed[cleared.cell] = Tile::Empty;
+        }
+        for fall in diff.fallen.iter() {
+            prop_assert!(fall.from.0 < fall.to.0 && fall.from.1 == fall.to.1);
+            prop_assert_eq!(replayed[fall.from], fall.tile);
+            prop_assert_eq!(replayed[fall.to], Tile::Empty);
+            replayed[fall.to] = fall.tile;
+            replayed[fall.from] = Tile::Empty;
+        }
+
+        prop_assert_eq!(replayed, after);
+    }
+}
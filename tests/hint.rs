@@ -0,0 +1,70 @@
+mod common;
+
+use common::tower;
+use spelltower::hint::{self, Hint};
+use spelltower::{words, Rules};
+
+// COWK scores the most on its own, but leaves nothing to play, while VOW leaves COW.
+const TOWER: &str = "OCB\nWVP\nIKG";
+
+fn hints(n: usize, candidates: usize) -> Vec<Hint> {
+    let (root, _) = words::load();
+    hint::hints(&tower(TOWER), root, &Rules::default(), n, candidates)
+}
+
+fn word(hint: &Hint) -> String {
+    hint.path.iter().map(|&(_, _, letter)| letter).collect()
+}
+
+#[test]
+fn ranks_by_lookahead() {
+    let hints = hints(10, 10);
+    assert_eq!(
+        hints.iter().map(word).collect::<Vec<_>>(),
+        ["VOW", "COWK", "COW"]
+    );
+    assert_eq!(
+        hints
+            .iter()
+            .map(|hint| (hint.score, hint.lookahead))
+            .collect::<Vec<_>>(),
+        [(33, 66), (64, 64), (30, 30)]
+    );
+}
+
+#[test]
+fn looks_ahead_greedily() {
+    let (root, _) = words::load();
+    let rules = Rules::default();
+    for hint in hints(10, 10) {
+        let mut rest = tower(TOWER);
+        assert_eq!(
+            hint.score,
+            spelltower::score_path(&rest, &hint.path, &rules)
+        );
+        spelltower::delete_path(&mut rest, &hint.path);
+        let greedy = spelltower::solve_greedy(&rest, root, &rules);
+        assert_eq!(
+            hint.lookahead,
+            hint.score + spelltower::score_solution(&rest, &greedy, &rules)
+        );
+    }
+}
+
+#[test]
+fn keeps_the_best_n() {
+    assert_eq!(hints(1, 10).iter().map(word).collect::<Vec<_>>(), ["VOW"]);
+    assert!(hints(0, 10).is_empty());
+}
+
+#[test]
+fn looks_ahead_from_the_best_candidates_only() {
+    // Only COWK is looked ahead from.
+    assert_eq!(hints(1, 1).iter().map(word).collect::<Vec<_>>(), ["COWK"]);
+    // At least `n` are looked ahead from, and VOW scores the second most on its own.
+    assert_eq!(
+        hints(2, 1).iter().map(word).collect::<Vec<_>>(),
+        ["VOW", "COWK"]
+    );
+    assert_eq!(hints(1, 2).iter().map(word).collect::<Vec<_>>(), ["VOW"]);
+}
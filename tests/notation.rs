@@ -0,0 +1,45 @@
+mod strategies;
+
+use proptest::prelude::*;
+use spelltower::{notation, Tile};
+
+proptest! {
+    #[test]
+    fn cell_names_round_trip(i in 0..100_000usize, j in 0..100_000usize) {
+        prop_assert_eq!(notation::parse_cell(&notation::cell_name((i, j))), Ok((i, j)));
+    }
+
+    #[test]
+    fn moves_round_trip((tower, path) in strategies::tower_and_path()) {
+        prop_assume!(!path.is_empty());
+        let cells = notation::parse(&notation::format(&tower, &path)).unwrap();
+        let expected = path
+            .iter()
+            .map(|&(i, j, letter)| notation::Cell {
+                cell: (i, j),
+                letter: (tower[[i, j]] == Tile::Wildcard).then_some(letter),
+            })
+            .collect::<Vec<_>>();
+        prop_assert_eq!(cells, expected);
+    }
+}
+
+#[test]
+fn names_cells_from_a1() {
+    assert_eq!(notation::cell_name((0, 0)), "A1");
+    assert_eq!(notation::cell_name((3, 2)), "C4");
+    assert_eq!(notation::cell_name((0, 25)), "Z1");
+    assert_eq!(notation::cell_name((0, 26)), "AA1");
+    assert_eq!(notation::parse_cell("c4"), Ok((3, 2)));
+}
+
+#[test]
+fn rejects_cells_that_are_not_names() {
+    for s in ["", "C", "4", "C0", "4C", "C-4", "1,1", "C4 "] {
+        assert_eq!(
+            notation::parse_cell(s),
+            Err(notation::Error::InvalidCell(s.to_string())),
+            "{s:?}"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::tower;
+use spelltower::planner::{self, Goal, Limits, Objective};
+use spelltower::{words, Rules, Tile};
+
+fn plan(tower: &spelltower::Tower, goal: &Goal, objective: Objective) -> Option<planner::Setup> {
+    let (root, max_len) = words::load();
+    planner::plan(
+        tower,
+        root,
+        &Rules::default(),
+        goal,
+        objective,
+        &Limits {
+            depth: 2,
+            beam_width: 50,
+            max_len,
+        },
+    )
+}
+
+fn word(path: &[(usize, usize, char)]) -> String {
+    path.iter().map(|&(_, _, letter)| letter).collect()
+}
+
+#[test]
+fn plays_a_word_that_is_already_there() {
+    let setup = plan(
+        &tower("CAT\n___"),
+        &Goal::Word("CAT".to_string()),
+        Objective::FewestMoves,
+    )
+    .unwrap();
+    assert!(setup.moves.is_empty());
+    assert_eq!(word(&setup.target), "CAT");
+}
+
+#[test]
+fn clears_the_way_for_a_word() {
+    // The C is cut off from the A until the row under it is played.
+    let start = tower(" C \nDOG\nAT_");
+    let (root, _) = words::load();
+    assert!(spelltower::find_word_paths(&start, "CAT").is_empty());
+
+    for objective in [Objective::FewestMoves, Objective::HighestScore] {
+        let setup = plan(&start, &Goal::Word("CAT".to_string()), objective).unwrap();
+        assert_eq!(word(&setup.target), "CAT");
+
+        let mut game = spelltower::game::Game::new(start.clone(), root, Rules::default());
+        for path in setup.moves.iter() {
+            game.play(path).unwrap();
+        }
+        assert_eq!(game.play(&setup.target).unwrap().path, setup.target);
+        assert_eq!(game.score(), setup.score);
+        if objective == Objective::FewestMoves {
+            assert_eq!(setup.moves.len(), 1);
+        }
+    }
+}
+
+#[test]
+fn gives_up_on_missing_letters() {
+    assert!(plan(
+        &tower(" C \nDOG\nAT_"),
+        &Goal::Word("ZEBRA".to_string()),
+        Objective::FewestMoves
+    )
+    .is_none());
+}
+
+#[test]
+fn finds_the_longest_word() {
+    let start = tower("PLANT\nCAT__");
+    let (root, _) = words::load();
+    let longest_now = spelltower::find_paths(&start, root)
+        .iter()
+        .map(|path| path.len())
+        .max()
+        .unwrap();
+
+    let setup = plan(&start, &Goal::Longest, Objective::FewestMoves).unwrap();
+    assert!(setup.target.len() >= longest_now);
+    assert!(root.contains(&word(&setup.target)));
+}
+
+#[test]
+fn has_letters_only_spells_letters() {
+    let mut tower = tower("CA?\n_#T");
+    assert!(spelltower::has_letters(&tower, "CAT"));
+    assert!(spelltower::has_letters(&tower, "ACTS"));
+    assert!(!spelltower::has_letters(&tower, "CATTY"));
+    assert!(!spelltower::has_letters(&tower, "cat"));
+    assert!(!spelltower::has_letters(&tower, "CAFÉ"));
+
+    tower[[1, 1]] = Tile::Letter {
+        letter: 'é',
+        bonus: false,
+    };
+    assert!(spelltower::has_letters(&tower, "CAT"));
+}
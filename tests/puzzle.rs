@@ -0,0 +1,174 @@
+use proptest::prelude::*;
+use spelltower::puzzle::{self, ErrorKind, LineEnding, Metadata};
+
+/// Any text on one line, leaning on characters that mean something in the format.
+fn line() -> impl Strategy<Value = String> {
+    proptest::collection::vec(
+        prop_oneof![
+            Just(' '),
+            Just(':'),
+            Just('\r'),
+            Just('x'),
+            Just('_'),
+            Just('?'),
+            proptest::char::range('0', '9'),
+            proptest::char::range('A', 'Z'),
+            proptest::char::range('a', 'z'),
+        ],
+        0..12,
+    )
+    .prop_map(|chars| chars.into_iter().collect())
+}
+
+fn row(w: usize) -> impl Strategy<Value = String> {
+    proptest::collection::vec(
+        prop_oneof![
+            Just(' '),
+            Just('_'),
+            Just('?'),
+            Just('#'),
+            proptest::char::range('A', 'Z'),
+            proptest::char::range('a', 'z'),
+        ],
+        w,
+    )
+    .prop_map(|chars| chars.into_iter().collect())
+}
+
+/// The text of a valid puzzle, with any mix of line endings.
+fn text() -> impl Strategy<Value = String> {
+    (0..6usize, 0..6usize)
+        .prop_flat_map(|(w, h)| {
+            (
+                line(),
+                Just(format!("{w}x{h}")),
+                proptest::collection::vec(row(w), h),
+                proptest::collection::vec(
+                    prop_oneof![
+                        line(),
+                        (line(), line()).prop_map(|(key, value)| format!("{key}: {value}")),
+                    ],
+                    0..4,
+                ),
+                proptest::collection::vec(prop_oneof![Just("\n"), Just("\r\n")], 12),
+                any::<bool>(),
+            )
+        })
+        .prop_map(|(title, dim, rows, metadata, endings, trailing_newline)| {
+            let lines = [title, dim]
+                .into_iter()
+                .chain(rows)
+                .chain(metadata)
+                .collect::<Vec<_>>();
+
+            // An empty last line is only there if there is a newline after it.
+            let trailing_newline = trailing_newline || lines.last().unwrap().is_empty();
+
+            let mut text = String::new();
+            for (k, line) in lines.iter().enumerate() {
+                text.push_str(line);
+                if k + 1 < lines.len() || trailing_newline {
+                    // A line that ends in \r before a plain \n would read as a CRLF line.
+                    text.push_str(if line.ends_with('\r') {
+                        "\r\n"
+                    } else {
+                        endings[k]
+                    });
+                }
+            }
+            text
+        })
+}
+
+proptest! {
+    #[test]
+    fn round_trips(text in text()) {
+        let puzzle = puzzle::parse(&text).unwrap();
+        prop_assert_eq!(puzzle.to_string(), text);
+    }
+}
+
+#[test]
+fn round_trips_crlf() {
+    let text = "SpellTower\r\n3x2\r\nAbC\r\nD_?\r\n";
+    let puzzle = puzzle::parse(text).unwrap();
+
+    assert_eq!(puzzle.line_endings, vec![LineEnding::CrLf; 4]);
+    assert!(puzzle.trailing_newline);
+    assert_eq!(puzzle.to_string(), text);
+}
+
+#[test]
+fn round_trips_mixed_line_endings() {
+    let text = "SpellTower\n3x2\r\nAbC\nD_?\r\nnote";
+    let puzzle = puzzle::parse(text).unwrap();
+
+    assert!(!puzzle.trailing_newline);
+    assert_eq!(puzzle.to_string(), text);
+}
+
+#[test]
+fn reads_metadata() {
+    let text = "SpellTower\n1x1\nA\n\nseed: 1234\nnot a field\n";
+    let puzzle = puzzle::parse(text).unwrap();
+
+    assert_eq!(
+        puzzle.metadata,
+        vec![
+            Metadata::Text(String::new()),
+            Metadata::Field {
+                key: "seed".to_string(),
+                value: "1234".to_string(),
+            },
+            Metadata::Text("not a field".to_string()),
+        ]
+    );
+    assert_eq!(puzzle.field("seed"), Some("1234"));
+    assert_eq!(puzzle.field("day"), None);
+    assert_eq!(puzzle.to_string(), text);
+}
+
+#[test]
+fn rejects_dimensions_that_would_not_round_trip() {
+    let error = puzzle::parse("SpellTower\n03x1\nABC\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidDimensions("03x1".to_string()));
+}
+
+#[test]
+fn rejects_huge_dimensions() {
+    for dim in [
+        format!("1x{}", usize::MAX),
+        format!("{}x2", usize::MAX),
+        format!("{0}x{0}", usize::MAX / 2),
+    ] {
+        let error = puzzle::parse(&format!("SpellTower\n{dim}\nAB\n")).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::InvalidDimensions(dim));
+    }
+}
+
+#[test]
+fn reports_where_rows_go_wrong() {
+    let error = puzzle::parse("SpellTower\n3x2\nABC\nDE\n").unwrap_err();
+    assert_eq!((error.line, error.column), (4, 3));
+    assert_eq!(
+        error.kind,
+        ErrorKind::RaggedRow {
+            expected: 3,
+            found: 2
+        }
+    );
+}
+
+#[test]
+fn draws_empty_puzzles() {
+    for text in ["SpellTower\n0x0\n", "SpellTower\n0x2\n\n\n"] {
+        let tower = puzzle::parse(text).unwrap().tower;
+        let pretty = spelltower::pretty_tower(
+            &tower,
+            &[],
+            &spelltower::Rules::default(),
+            &spelltower::Style::PLAIN,
+        );
+        assert!(pretty.starts_with("╔╗"), "{pretty}");
+    }
+}
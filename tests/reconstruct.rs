@@ -0,0 +1,89 @@
+mod common;
+
+use common::tower;
+use spelltower::reconstruct::{self, Error, Word};
+use spelltower::{words, Rules};
+
+fn word(word: &str, cell: Option<(usize, usize)>) -> Word {
+    Word {
+        word: word.to_string(),
+        cell,
+    }
+}
+
+fn reconstruct(
+    tower: &spelltower::Tower,
+    words: &[Word],
+    beam_width: usize,
+) -> Result<spelltower::Solution, Error> {
+    let (root, _) = words::load();
+    reconstruct::reconstruct(tower, root, &Rules::default(), words, beam_width)
+}
+
+#[test]
+fn traces_words_in_order() {
+    let tower = tower("DOG\nCAT");
+    let solution = reconstruct(&tower, &[word("CAT", None), word("DOG", None)], 1).unwrap();
+    assert_eq!(
+        solution,
+        vec![
+            vec![(1, 0, 'C'), (1, 1, 'A'), (1, 2, 'T')],
+            vec![(1, 0, 'D'), (1, 1, 'O'), (1, 2, 'G')],
+        ]
+    );
+}
+
+#[test]
+fn uses_the_given_tile() {
+    // Either A can be played, so only the hint says which.
+    let tower = tower("CAT\n_A_");
+    for cell in [(0, 1), (1, 1)] {
+        let solution = reconstruct(&tower, &[word("CAT", Some(cell))], 1).unwrap();
+        assert!(solution[0].iter().any(|&(i, j, _)| (i, j) == cell));
+    }
+}
+
+#[test]
+fn says_why_words_cannot_be_played() {
+    let dog_cat = tower("DOG\nCAT");
+
+    assert_eq!(
+        reconstruct(&dog_cat, &[word("CAT", None)], 0),
+        Err(Error::ZeroBeamWidth)
+    );
+    assert_eq!(
+        reconstruct(&dog_cat, &[word("QQQ", None)], 1),
+        Err(Error::NotAWord {
+            index: 0,
+            word: "QQQ".to_string()
+        })
+    );
+    assert_eq!(
+        reconstruct(&dog_cat, &[word("CAT", None), word("CAT", None)], 1),
+        Err(Error::MissingLetters {
+            index: 1,
+            word: "CAT".to_string()
+        })
+    );
+    assert_eq!(
+        reconstruct(&tower("C_AT"), &[word("CAT", None)], 1),
+        Err(Error::NoPath {
+            index: 0,
+            word: "CAT".to_string()
+        })
+    );
+
+    let error = reconstruct(&dog_cat, &[word("CAT", Some((0, 0)))], 1).unwrap_err();
+    assert_eq!(
+        error,
+        Error::CellNotUsed {
+            index: 0,
+            word: "CAT".to_string(),
+            cell: (0, 0)
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "word 1 (CAT) cannot be traced through A1"
+    );
+}
@@ -0,0 +1,59 @@
+//! Slow but obviously correct versions of the engine, to check the real thing against.
+
+use spelltower::{Tile, Tower};
+
+pub fn deletable(
+    tower: &Tower,
+    path: &[(usize, usize, char)],
+) -> std::collections::HashSet<(usize, usize)> {
+    let (n, m) = tower.dim();
+
+    let rare_rows = path
+        .iter()
+        .filter(|&&(_, _, letter)| matches!(letter, 'J' | 'Q' | 'X' | 'Z'))
+        .map(|&(i, _, _)| i)
+        .collect::<Vec<_>>();
+
+    let mut collected = std::collections::HashSet::new();
+    for i in 0..n {
+        for j in 0..m {
+            let tile = tower[[i, j]];
+
+            let on_path = path.iter().any(|&(pi, pj, _)| (pi, pj) == (i, j));
+            let touches_path = path
+                .iter()
+                .any(|&(pi, pj, _)| pi.abs_diff(i) + pj.abs_diff(j) == 1);
+
+            if on_path
+                || (!tile.is_empty() && rare_rows.contains(&i))
+                || (!tile.is_empty() && touches_path && (tile == Tile::Blank || path.len() >= 5))
+            {
+                collected.insert((i, j));
+            }
+        }
+    }
+
+    collected
+}
+
+/// Clears everything `path` clears, then drops every column's remaining tiles to the bottom.
+pub fn delete_path(tower: &mut Tower, path: &[(usize, usize, char)]) {
+    let (n, _) = tower.dim();
+
+    for (i, j) in deletable(tower, path) {
+        tower[[i, j]] = Tile::Empty;
+    }
+
+    for mut column in tower.columns_mut() {
+        let tiles = column
+            .iter()
+            .cloned()
+            .filter(|tile| !tile.is_empty())
+            .collect::<Vec<_>>();
+        for (i, tile) in column.iter_mut().enumerate() {
+            *tile = (i + tiles.len())
+                .checked_sub(n)
+                .map_or(Tile::Empty, |k| tiles[k]);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "render")]
+
+mod common;
+
+use common::tower;
+use spelltower::{render, Rules};
+
+#[test]
+fn draws_every_letter() {
+    let tower = tower("DOG\nCAT");
+    let svg = render::svg(&tower, &[], &Rules::default());
+    for letter in "DOGCAT".chars() {
+        assert!(
+            svg.contains(&format!(">{letter}</text>")),
+            "{letter} missing"
+        );
+    }
+}
+
+#[test]
+fn heads_moves_with_the_word_and_score() {
+    let tower = tower("DOG\nCAT");
+    let path = [(1, 0, 'C'), (1, 1, 'A'), (1, 2, 'T')];
+    let rules = Rules::default();
+    let svg = render::svg(&tower, &path, &rules);
+    let score = spelltower::score_path(&tower, &path, &rules);
+    assert!(svg.contains(&format!(">{score}</tspan>")));
+    assert!(svg.contains("CAT <tspan"));
+}
+
+#[test]
+fn draws_pngs_only_with_fonts() {
+    let svg = render::svg(&tower("DOG\nCAT"), &[], &Rules::default());
+    match render::png(&svg) {
+        Ok(png) => assert!(png.starts_with(b"\x89PNG\r\n\x1a\n")),
+        Err(e) => assert!(matches!(e, render::Error::NoFonts), "{e}"),
+    }
+}
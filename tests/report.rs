@@ -0,0 +1,44 @@
+mod common;
+
+use common::tower;
+use spelltower::{report, Rules};
+
+/// The JSON the page replays.
+fn data(page: &str) -> serde_json::Value {
+    let start = r#"<script id="replay" type="application/json">"#;
+    let data = &page[page.find(start).unwrap() + start.len()..];
+    serde_json::from_str(&data[..data.find("</script>").unwrap()]).unwrap()
+}
+
+#[test]
+fn fills_in_the_template() {
+    let cat = tower("CAT\n_O_");
+    let solution = spelltower::find_word_paths(&cat, "CAT");
+    let page = report::html("Daily", &cat, &solution[..1].to_vec(), &Rules::default());
+
+    assert!(page.contains("<title>Daily</title>"));
+    assert!(page.contains(r#"if (c === "_") tile.classList.add("blank");"#));
+    assert!(!page.contains("{{"));
+
+    let data = data(&page);
+    assert_eq!(data["title"], "Daily");
+    assert_eq!(data["moves"][0]["word"], "CAT");
+    assert_eq!(
+        data["moves"][0]["before"],
+        serde_json::json!(["CAT", "_O_"])
+    );
+}
+
+#[test]
+fn escapes_hostile_titles() {
+    let cat = tower("CAT");
+    let title = "</script><b>&</b> {{data}} {{blank}}";
+    let page = report::html(title, &cat, &vec![], &Rules::default());
+
+    let escaped = "&lt;/script&gt;&lt;b&gt;&amp;&lt;/b&gt; {{data}} {{blank}}";
+    assert!(page.contains(&format!("<title>{escaped}</title>")));
+    assert!(page.contains(&format!("<h1>{escaped}</h1>")));
+    // Only the data and the code close their script elements.
+    assert_eq!(page.matches("</script>").count(), 2);
+    assert_eq!(data(&page)["title"], title);
+}
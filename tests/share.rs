@@ -0,0 +1,64 @@
+mod common;
+
+use common::tower;
+use spelltower::{share, Rules};
+
+/// Plays the word on the bottom row, `count` times.
+fn bottom_rows(height: usize, count: usize) -> spelltower::Solution {
+    (0..count)
+        .map(|_| (0..3).map(|j| (height - 1, j, 'A')).collect())
+        .collect()
+}
+
+#[test]
+fn finds_which_move_cleared_each_tile() {
+    let tower = tower("DOG\nCAT\n___");
+    // CAT clears the blanks below it, then DOG falls to the bottom.
+    let solution = vec![
+        vec![(1, 0, 'C'), (1, 1, 'A'), (1, 2, 'T')],
+        vec![(2, 0, 'D'), (2, 1, 'O'), (2, 2, 'G')],
+    ];
+    let cleared_by = share::cleared_by(&tower, &solution);
+    assert_eq!(
+        cleared_by,
+        ndarray::array![
+            [Some(1), Some(1), Some(1)],
+            [Some(0), Some(0), Some(0)],
+            [Some(0), Some(0), Some(0)],
+        ]
+    );
+}
+
+#[test]
+fn shows_only_the_best_end_bonus() {
+    let cleared = tower("DOG\nCAT");
+    let card = share::card("Day 1", &cleared, &bottom_rows(2, 2), &Rules::default());
+    assert!(card.contains("Tower cleared!"));
+    assert!(!card.contains("Almost there!"));
+
+    let almost = tower("DOG\nCAT\nPIG");
+    let card = share::card("Day 1", &almost, &bottom_rows(3, 1), &Rules::default());
+    assert!(!card.contains("Tower cleared!"));
+    assert!(card.contains("Almost there!"));
+}
+
+#[test]
+fn colours_short_solutions() {
+    let tower = tower(" OG\nCAT\nPIG");
+    let card = share::card("Day 1", &tower, &bottom_rows(3, 2), &Rules::default());
+    let grid = card.lines().skip(3).collect::<Vec<_>>();
+    assert_eq!(grid, ["⬜⬛⬛", "🟧🟧🟧", "🟥🟥🟥"]);
+}
+
+#[test]
+fn numbers_solutions_with_more_moves_than_colours() {
+    let rows = [
+        " AT", "DOG", "PIG", "COW", "HEN", "FOX", "BEE", "ANT", "ELK", "EMU", "OWL",
+    ];
+    let tower = tower(&rows.join("\n"));
+    let card = share::card("Day 1", &tower, &bottom_rows(11, 10), &Rules::default());
+    let grid = card.lines().skip(3).collect::<Vec<_>>();
+    assert_eq!(grid[0], " .  #  #");
+    assert_eq!(grid[1], "10 10 10");
+    assert_eq!(grid[10], " 1  1  1");
+}
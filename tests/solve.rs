@@ -0,0 +1,62 @@
+use rand::SeedableRng as _;
+use spelltower::{puzzle, words, Rules};
+
+fn tower(text: &str) -> spelltower::Tower {
+    puzzle::parse(text).unwrap().tower
+}
+
+#[test]
+fn nudging_an_empty_solution_does_nothing() {
+    let (root, _) = words::load();
+    let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(0);
+
+    for tower in [
+        tower("SpellTower\n2x2\n__\n_Q\n"),
+        tower("SpellTower\n3x1\nCAT\n"),
+    ] {
+        let mut solution = vec![];
+        spelltower::nudge_solution(&tower, root, &Rules::default(), &mut solution, &mut rng);
+        assert!(solution.is_empty());
+    }
+}
+
+#[test]
+fn nudging_keeps_solutions_legal() {
+    let (root, _) = words::load();
+    let rules = Rules::default();
+    let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(0);
+    let tower = tower("SpellTower\n5x3\nQUIZ_\nAbCDE\nFGH?I\n");
+
+    let mut solution = spelltower::solve_greedy(&tower, root, &rules);
+    assert!(!solution.is_empty());
+    for _ in 0..20 {
+        spelltower::nudge_solution(&tower, root, &rules, &mut solution, &mut rng);
+
+        let mut game = spelltower::game::Game::new(tower.clone(), root, rules);
+        for path in solution.iter() {
+            game.play(path).unwrap();
+        }
+        assert!(game.is_over());
+    }
+}
+
+#[test]
+fn cumulative_scores_add_up_to_the_score() {
+    let (root, _) = words::load();
+    let rules = Rules::default();
+    let tower = tower("SpellTower\n5x3\nQUIZ_\nAbCDE\nFGH?I\n");
+
+    let solution = spelltower::solve_greedy(&tower, root, &rules);
+    let scores = spelltower::cumulative_scores(&tower, &solution, &rules);
+    assert_eq!(scores.len(), solution.len());
+    assert!(scores.is_sorted());
+
+    let mut after = tower.clone();
+    for path in solution.iter() {
+        spelltower::delete_path(&mut after, path);
+    }
+    assert_eq!(
+        scores.last().unwrap() + spelltower::end_bonus(&after),
+        spelltower::score_solution(&tower, &solution, &rules)
+    );
+}
@@ -0,0 +1,81 @@
+//! Towers and paths for the property tests.
+
+use proptest::prelude::*;
+use spelltower::{Tile, Tower};
+
+pub fn tile() -> impl Strategy<Value = Tile> {
+    prop_oneof![
+        3 => Just(Tile::Empty),
+        1 => Just(Tile::Blank),
+        1 => Just(Tile::Wildcard),
+        8 => (proptest::char::range('A', 'Z'), proptest::bool::weighted(0.1))
+            .prop_map(|(letter, bonus)| Tile::Letter { letter, bonus }),
+    ]
+}
+
+pub fn tower() -> impl Strategy<Value = Tower> {
+    (1..=6usize, 1..=6usize).prop_flat_map(|(n, m)| {
+        proptest::collection::vec(tile(), n * m)
+            .prop_map(move |tiles| Tower::from_shape_vec((n, m), tiles).unwrap())
+    })
+}
+
+/// A walk over adjacent, distinct, playable tiles. Paths don't have to spell words: clearing and
+/// gravity don't care.
+pub fn tower_and_path() -> impl Strategy<Value = (Tower, Vec<(usize, usize, char)>)> {
+    (
+        tower(),
+        any::<proptest::sample::Index>(),
+        proptest::collection::vec((0..8usize, proptest::char::range('A', 'Z')), 0..8),
+    )
+        .prop_map(|(tower, start, steps)| {
+            let playable = |(i, j): (usize, usize)| match tower.get([i, j]) {
+                Some(Tile::Letter { letter, .. }) => Some(*letter),
+                Some(Tile::Wildcard) => Some('A'),
+                _ => None,
+            };
+
+            let cells = tower
+                .indexed_iter()
+                .map(|(cell, _)| cell)
+                .filter(|&cell| playable(cell).is_some())
+                .collect::<Vec<_>>();
+            if cells.is_empty() {
+                return (tower, vec![]);
+            }
+
+            let (i, j) = *start.get(&cells);
+            let mut path = vec![(i, j, playable((i, j)).unwrap())];
+            for (direction, wildcard) in steps {
+                let &(oi, oj, _) = path.last().unwrap();
+                let (di, dj) = [
+                    (-1, -1),
+                    (-1, 0),
+                    (-1, 1),
+                    (0, -1),
+                    (0, 1),
+                    (1, -1),
+                    (1, 0),
+                    (1, 1),
+                ][direction];
+                let (Some(i), Some(j)) = (oi.checked_add_signed(di), oj.checked_add_signed(dj))
+                else {
+                    continue;
+                };
+                if path.iter().any(|&(pi, pj, _)| (pi, pj) == (i, j)) {
+                    continue;
+                }
+                let Some(letter) = playable((i, j)) else {
+                    continue;
+                };
+                let letter = if tower[[i, j]] == Tile::Wildcard {
+                    wildcard
+                } else {
+                    letter
+                };
+                path.push((i, j, letter));
+            }
+
+            (tower, path)
+        })
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use common::tower;
+use spelltower::{pretty_tower, style, Rules, Style};
+
+const CAT: [(usize, usize, char); 3] = [(1, 0, 'C'), (1, 1, 'A'), (1, 2, 'T')];
+
+#[test]
+fn leaves_labels_off_by_default() {
+    let tower = tower("DOG\nCAT");
+    let rules = Rules::default();
+    assert!(!Style::default().labels);
+    assert_eq!(
+        pretty_tower(&tower, &[], &rules, &Style::PLAIN),
+        "╔═══════════╗\n║ D   O   G ║\n║           ║\n║ C   A   T ║\n╚═══════════╝"
+    );
+}
+
+#[test]
+fn labels_rows_and_columns_in_move_notation() {
+    let tower = tower("DOG\nCAT");
+    let style = Style {
+        labels: true,
+        ..Style::PLAIN
+    };
+    let pretty = pretty_tower(&tower, &[], &Rules::default(), &style);
+    let lines = pretty.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "   A   B   C  ");
+    assert!(lines[2].starts_with("1║"));
+    assert!(lines[4].starts_with("2║"));
+}
+
+#[test]
+fn marks_tiles_without_colours() {
+    let tower = tower("_OG\nCAT");
+    let pretty = pretty_tower(&tower, &CAT, &Rules::default(), &Style::PLAIN);
+    assert!(!pretty.contains('\x1b'));
+    assert!(pretty.contains("[C]"));
+    assert!(pretty.contains("*░*"));
+}
+
+#[test]
+fn colours_tiles_in_the_theme() {
+    let tower = tower("DOG\nCAT");
+    let style = Style {
+        theme: Some(style::Theme::BLUE),
+        ..Style::default()
+    };
+    let pretty = pretty_tower(&tower, &CAT, &Rules::default(), &style);
+    assert!(pretty.contains(&format!("\x1b[{}m C \x1b[0m", style::Theme::BLUE.first)));
+    assert!(!pretty.contains("[C]"));
+    assert_eq!(style.paint("x", |t| t.link), "\x1b[1;34mx\x1b[0m");
+    assert_eq!(Style::PLAIN.paint("x", |t| t.link), "x");
+}
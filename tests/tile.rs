@@ -0,0 +1,36 @@
+use spelltower::Tile;
+
+#[test]
+fn chars_round_trip() {
+    for (c, tile) in [
+        (' ', Tile::Empty),
+        ('_', Tile::Blank),
+        ('?', Tile::Wildcard),
+        (
+            'Q',
+            Tile::Letter {
+                letter: 'Q',
+                bonus: false,
+            },
+        ),
+        (
+            'q',
+            Tile::Letter {
+                letter: 'Q',
+                bonus: true,
+            },
+        ),
+        ('#', Tile::Other('#')),
+        ('é', Tile::Other('é')),
+    ] {
+        assert_eq!(Tile::from(c), tile, "{c:?}");
+        assert_eq!(char::from(tile), c, "{tile:?}");
+    }
+}
+
+#[test]
+fn every_char_round_trips() {
+    for c in (0..=0x2fff).filter_map(char::from_u32) {
+        assert_eq!(char::from(Tile::from(c)), c);
+    }
+}
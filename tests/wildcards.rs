@@ -0,0 +1,63 @@
+mod common;
+
+use common::tower;
+use spelltower::{words, Rules, Tile, WildcardScoring};
+
+const QUIZ: &[(usize, usize, char)] = &[(0, 0, 'Q'), (0, 1, 'U'), (0, 2, 'I'), (0, 3, 'Z')];
+
+#[test]
+fn wildcards_play_as_any_letter() {
+    let tower = tower("Q?IZ");
+    let (root, _) = words::load();
+
+    assert!(Tile::Wildcard.can_be('U'));
+    assert!(!Tile::Wildcard.can_be('?'));
+    assert!(spelltower::find_paths(&tower, root).contains(&QUIZ.to_vec()));
+    assert_eq!(
+        spelltower::find_word_paths(&tower, "QUIZ"),
+        vec![QUIZ.to_vec()]
+    );
+}
+
+#[test]
+fn wildcards_score_by_the_rules() {
+    let tower = tower("Q?IZ");
+    let zero = Rules {
+        wildcard_scoring: WildcardScoring::Zero,
+    };
+    let full = Rules {
+        wildcard_scoring: WildcardScoring::Full,
+    };
+
+    // Q + I + Z, then U on top when wildcards are worth their letter, times 4 letters.
+    assert_eq!(
+        spelltower::score_path(&tower, QUIZ, &zero),
+        (12 + 1 + 11) * 4
+    );
+    assert_eq!(
+        spelltower::score_path(&tower, QUIZ, &full),
+        (12 + 1 + 1 + 11) * 4
+    );
+    assert_eq!(Rules::default(), zero);
+}
+
+#[test]
+fn wildcards_cleared_without_being_played_are_worth_nothing() {
+    // The wildcard is next to a 5-letter word but not in it, so there is no letter to score it as.
+    let tower = tower("QUIET\n????_");
+    let path = [
+        (0, 0, 'Q'),
+        (0, 1, 'U'),
+        (0, 2, 'I'),
+        (0, 3, 'E'),
+        (0, 4, 'T'),
+    ];
+    let full = Rules {
+        wildcard_scoring: WildcardScoring::Full,
+    };
+
+    assert_eq!(
+        spelltower::score_path(&tower, &path, &full),
+        spelltower::score_path(&tower, &path, &Rules::default())
+    );
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::tower;
+use spelltower::{word_finder, words, Rules};
+
+fn find(tower: &spelltower::Tower, word: &str) -> word_finder::Word {
+    let (root, _) = words::load();
+    word_finder::find_words(tower, root, &Rules::default())
+        .into_iter()
+        .find(|w| w.word == word)
+        .unwrap_or_else(|| panic!("{word} not found"))
+}
+
+#[test]
+fn long_words_clear_the_letters_next_to_them() {
+    let plants = find(&tower("PLANTS"), "PLANT");
+    assert!(plants.long_word_clear);
+    assert_eq!(plants.cleared, 6);
+
+    // Blanks next to the word would go whatever its length.
+    let blanks = find(&tower("PLANT\n_____"), "PLANT");
+    assert!(!blanks.long_word_clear);
+    assert_eq!(blanks.cleared, 10);
+
+    // A tile in the row of the Q that is next to the word too.
+    let quite = find(&tower("QUITEA"), "QUITE");
+    assert!(quite.rare_letter_clear);
+    assert!(quite.long_word_clear);
+
+    // Only a tile in the row of the Q, away from the word.
+    let quite = find(&tower("QUITE A"), "QUITE");
+    assert!(quite.rare_letter_clear);
+    assert!(!quite.long_word_clear);
+    assert_eq!(quite.cleared, 6);
+
+    // Nothing around the word at all.
+    assert!(!find(&tower("PLANT"), "PLANT").long_word_clear);
+
+    // Too short, even with blanks to clear.
+    let ant = find(&tower("ANT\n___"), "ANT");
+    assert!(!ant.long_word_clear);
+    assert_eq!(ant.cleared, 6);
+}
+
+#[test]
+fn contains_letters_counts_repeats() {
+    assert!(word_finder::contains_letters("TEETH", "EE"));
+    assert!(word_finder::contains_letters("TEETH", "HTE"));
+    assert!(word_finder::contains_letters("TEETH", ""));
+    assert!(!word_finder::contains_letters("THEM", "EE"));
+    assert!(!word_finder::contains_letters("TEETH", "EEE"));
+}